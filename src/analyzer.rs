@@ -2,40 +2,205 @@
 use anyhow::{ Context, Result };
 use colored::*;
 use indicatif::{ ProgressBar, ProgressStyle };
-use regex::Regex;
-use walkdir::{ DirEntry, WalkDir };
+use rayon::prelude::*;
+use std::collections::{ BTreeMap, HashMap, HashSet };
 use std::fs;
 use std::path::{ Path, PathBuf };
-
+use std::sync::{ Arc, Mutex, OnceLock };
+
+use crate::architecture;
+use crate::boundaries;
+use crate::cache::{ self, ParseCache };
+use crate::config::{ self, Config, FileFilter };
+use crate::cycles;
+use crate::links;
 use crate::models::*;
+use crate::resolver::ModuleResolver;
+use crate::route_audit;
+use crate::scanner::{ self, FileIndex, IndexedFile, ScanOptions };
+use crate::template::{ self, Selector };
+use crate::utils::fs_utils;
+use crate::workspace::Workspace;
+use crate::typescript::{ self, ClassDecl, Expr, ImportKind, ObjectLit, SourceFile };
+
+// Guards against re-export cycles between barrels
+const MAX_REEXPORT_DEPTH: usize = 16;
 
 pub struct Analyzer {
     root_path: PathBuf,
-    include_tests: bool,
-    include_styles: bool,
-    include_node_modules: bool,
-    max_depth: usize,
+    options: ScanOptions,
+    // Loaded from the file index when first needed
+    workspace: OnceLock<Option<Workspace>>,
+    // Only files of this workspace project are analyzed when set
+    project: Option<String>,
+    config: Config,
+    // Globs given to `exclude`, kept across `apply_config`
+    excludes: Vec<String>,
+    // Built by the first analysis and shared by the rest
+    index: OnceLock<FileIndex>,
+    // Parsed sources, so a file is read once however many analyses use it
+    sources: Mutex<HashMap<PathBuf, Arc<SourceFile>>>,
+    // Parses from earlier runs, when enabled
+    cache: Option<ParseCache>,
+    // INFO lines and spinners on stderr; off so embedding programs stay quiet
+    show_progress: bool,
+}
+
+// The routes of one array literal, with the variable it is declared as
+struct RouteArray {
+    name: Option<String>,
+    routes: Vec<Route>,
+}
+
+// Which files an `Analyzer` reads. The default analyzes every source file
+// at any depth, leaving out tests, styles and node_modules.
+#[derive(Debug, Default, Clone)]
+pub struct AnalyzerOptions {
+    pub include_tests: bool,
+    pub include_styles: bool,
+    pub include_node_modules: bool,
+    // Directory levels below the root to descend; unlimited when None
+    pub max_depth: Option<usize>,
 }
 
 impl Analyzer {
-    pub fn new(
-        root_path: PathBuf,
-        include_tests: bool,
-        include_styles: bool,
-        include_node_modules: bool,
-        max_depth: usize
-    ) -> Self {
+    pub fn new(root_path: PathBuf, options: AnalyzerOptions) -> Self {
         Self {
             root_path,
-            include_tests,
-            include_styles,
-            include_node_modules,
-            max_depth,
+            options: ScanOptions {
+                include_tests: options.include_tests,
+                include_styles: options.include_styles,
+                include_node_modules: options.include_node_modules,
+                max_depth: options.max_depth,
+                ..ScanOptions::default()
+            },
+            workspace: OnceLock::new(),
+            project: None,
+            config: Config::default(),
+            excludes: Vec::new(),
+            index: OnceLock::new(),
+            sources: Mutex::new(HashMap::new()),
+            cache: None,
+            show_progress: false,
         }
     }
 
+    pub fn show_progress(&mut self, show: bool) {
+        self.show_progress = show;
+    }
+
+    // Applies the include/exclude globs, file types and layers of a
+    // `.ngstructure.toml`, replacing an earlier config. Globs given to
+    // `exclude` stay in effect whichever of the two is called first.
+    pub fn apply_config(&mut self, config: Config) -> Result<()> {
+        let mut filter = FileFilter::new(&config, &self.root_path)?;
+        filter.set_root_excludes(&self.excludes)?;
+        self.options.filter = filter;
+        self.options.file_types = config.file_types.clone();
+        self.config = config;
+        self.index = OnceLock::new();
+        self.workspace = OnceLock::new();
+        Ok(())
+    }
+
+    // Excludes files matching globs relative to the analyzed root, on top of
+    // the config, the ignore files and the globs of earlier calls
+    pub fn exclude(&mut self, patterns: &[String]) -> Result<()> {
+        let excludes = [self.excludes.as_slice(), patterns].concat();
+        self.options.filter.set_root_excludes(&excludes)?;
+        self.excludes = excludes;
+        self.index = OnceLock::new();
+        self.workspace = OnceLock::new();
+        Ok(())
+    }
+
+    // Reuses the parses of unchanged files from earlier runs, stored in `dir`
+    pub fn use_cache(&mut self, dir: &Path) {
+        self.cache = Some(ParseCache::new(dir));
+    }
+
+    // Where `use_cache` keeps parses for `root` unless told otherwise
+    pub fn default_cache_dir(root: &Path) -> PathBuf {
+        root.join(cache::DEFAULT_DIR)
+    }
+
+    // Deletes the parses `use_cache` stored in `dir`. A directory the cache
+    // did not create is refused.
+    pub fn clear_cache(dir: &Path) -> Result<()> {
+        ParseCache::clear(dir)
+    }
+
+    // Forgets the parses of changed files and rescans the tree, so the next
+    // analysis sees files as they are now. Returns whether any of the paths
+    // was or now is a project file or directory, or is a workspace or
+    // tsconfig file; others (ignored or excluded files) do not change the
+    // results.
+    pub fn refresh(&mut self, changed: &[PathBuf]) -> bool {
+        // Watchers report absolute paths; the index joins them to the root
+        // as given
+        let absolute_root = std::path::absolute(&self.root_path).unwrap_or_else(|_| self.root_path.clone());
+        let changed: Vec<PathBuf> = changed
+            .iter()
+            .map(|path| match path.strip_prefix(&absolute_root) {
+                Ok(relative) => self.root_path.join(relative),
+                Err(_) => path.clone(),
+            })
+            .collect();
+
+        let previous = self.index.take().unwrap_or_default();
+
+        let sources = self.sources.get_mut().unwrap();
+        for path in &changed {
+            sources.remove(path);
+        }
+
+        let is_workspace_file = |path: &PathBuf| {
+            path.file_name().is_some_and(|name| name == "angular.json" || name == "project.json")
+        };
+        let workspace_changed = changed.iter().any(is_workspace_file);
+        if workspace_changed {
+            self.workspace = OnceLock::new();
+        }
+
+        // Import resolution reads tsconfig files anew on every analysis, even
+        // when the include globs leave them out of the index
+        let is_tsconfig = |path: &PathBuf| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("tsconfig") && name.ends_with(".json"))
+        };
+
+        let index = self.file_index();
+        workspace_changed
+            || changed.iter().any(|path| is_tsconfig(path) || previous.contains(path) || index.contains(path))
+    }
+
+    // The directories the scan of the tree enters; changes anywhere else
+    // never affect the results
+    pub fn scanned_dirs(&self) -> &[PathBuf] {
+        &self.file_index().dirs
+    }
+
+    // Restricts the analysis to one project of the workspace
+    pub fn select_project(&mut self, name: &str) -> Result<()> {
+        let Some(workspace) = self.workspace() else {
+            anyhow::bail!("'{}' has no workspace projects to select project '{}' from", self.root_path.display(), name);
+        };
+
+        if workspace.project(name).is_none() {
+            anyhow::bail!(
+                "Unknown project '{}'. Workspace projects: {}",
+                name,
+                workspace.project_names().join(", ")
+            );
+        }
+
+        self.project = Some(name.to_string());
+        Ok(())
+    }
+
     pub fn analyze_structure(&mut self) -> Result<ProjectStructure> {
-        println!("{} Analyzing project structure...", "INFO:".blue().bold());
+        self.info("Analyzing project structure...");
 
         let root_name = self.root_path
             .file_name()
@@ -50,74 +215,320 @@ impl Analyzer {
             files: Vec::new(),
         };
 
-        self.scan_directory(&self.root_path, &mut root, 0)?;
+        for file in self.project_files() {
+            let Some(file_name) = file.path.file_name() else { continue };
+
+            // Walk down to the file's directory, adding the directories on the way
+            let mut node = &mut root;
+            if let Some(parent) = file.relative_path.parent() {
+                for dir in parent.components() {
+                    let name = dir.as_os_str().to_string_lossy().to_string();
+                    let index = match node.directories.iter().position(|d| d.name == name) {
+                        Some(index) => index,
+                        None => {
+                            node.directories.push(DirectoryNode {
+                                path: node.path.join(&name),
+                                name,
+                                directories: Vec::new(),
+                                files: Vec::new(),
+                            });
+                            node.directories.len() - 1
+                        }
+                    };
+                    node = &mut node.directories[index];
+                }
+            }
+
+            node.files.push(FileNode {
+                name: file_name.to_string_lossy().to_string(),
+                path: file.path.clone(),
+                file_type: file.file_type.clone(),
+            });
+        }
+
+        sort_directory(&mut root);
 
         Ok(ProjectStructure { root })
     }
 
     pub fn analyze_components(&mut self) -> Result<Vec<Component>> {
-        println!("{} Analyzing components...", "INFO:".blue().bold());
+        self.info("Analyzing components...");
 
-        let mut components = Vec::new();
         let progress = self.create_progress_bar("Scanning for components");
 
-        // Find all component files
-        for entry in self.walk_project_files() {
-            let path = entry.path();
-            if let Some(file_name) = path.file_name().and_then(|f| f.to_str()) {
-                if file_name.ends_with(".component.ts") && !file_name.ends_with(".spec.ts") {
-                    let component = self.parse_component(path)?;
-                    components.push(component);
-                    progress.inc(1);
-                }
-            }
-        }
+        // Components are standalone by default from Angular 19 onwards
+        let standalone_by_default = self.angular_major_version().is_some_and(|v| v >= 19);
+
+        // Parse all component files
+        let components: Vec<Component> = self
+            .parse_files(&progress, |file| file.file_type == FileType::Component, |path| {
+                self.parse_components(path, standalone_by_default)
+            })?
+            .into_iter()
+            .flatten()
+            .collect();
 
         progress.finish_with_message(format!("Found {} components", components.len()));
 
         Ok(components)
     }
 
-    pub fn analyze_services(&mut self) -> Result<Vec<Service>> {
-        println!("{} Analyzing services...", "INFO:".blue().bold());
+    pub fn analyze_directives(&mut self) -> Result<Vec<Directive>> {
+        self.info("Analyzing directives...");
 
-        let mut services = Vec::new();
-        let progress = self.create_progress_bar("Scanning for services");
+        let progress = self.create_progress_bar("Scanning for directives");
 
-        // Find all service files
-        for entry in self.walk_project_files() {
-            let path = entry.path();
-            if let Some(file_name) = path.file_name().and_then(|f| f.to_str()) {
-                if file_name.ends_with(".service.ts") && !file_name.ends_with(".spec.ts") {
-                    let service = self.parse_service(path)?;
-                    services.push(service);
+        let standalone_by_default = self.angular_major_version().is_some_and(|v| v >= 19);
+
+        // Parse all directive files
+        let directives: Vec<Directive> = self
+            .parse_files(&progress, |file| file.file_type == FileType::Directive, |path| {
+                self.parse_directives(path, standalone_by_default)
+            })?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        progress.finish_with_message(format!("Found {} directives", directives.len()));
+
+        Ok(directives)
+    }
+
+    pub fn analyze_pipes(&mut self) -> Result<Vec<Pipe>> {
+        self.info("Analyzing pipes...");
+
+        let progress = self.create_progress_bar("Scanning for pipes");
+
+        let standalone_by_default = self.angular_major_version().is_some_and(|v| v >= 19);
+
+        // Parse all pipe files
+        let pipes: Vec<Pipe> = self
+            .parse_files(&progress, |file| file.file_type == FileType::Pipe, |path| {
+                self.parse_pipes(path, standalone_by_default)
+            })?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        progress.finish_with_message(format!("Found {} pipes", pipes.len()));
+
+        Ok(pipes)
+    }
+
+    pub fn analyze_render_tree(&mut self) -> Result<RenderTree> {
+        let components = self.analyze_components()?;
+        let directives = self.analyze_directives()?;
+
+        self.info("Analyzing templates...");
+
+        let mut selectables = Vec::new();
+        for component in &components {
+            if let Some(ref selector) = component.selector {
+                selectables.push((&component.name, RenderKind::Component, selector, Selector::parse(selector)));
+            }
+        }
+        for directive in &directives {
+            if let Some(ref selector) = directive.selector {
+                selectables.push((&directive.name, RenderKind::Directive, selector, Selector::parse(selector)));
+            }
+        }
+
+        let progress = self.create_progress_bar("Scanning templates");
+
+        let edges: Vec<RenderEdge> = components
+            .par_iter()
+            .flat_map_iter(|component| {
+                let template = match component.template_kind {
+                    // A missing template file simply contributes no children
+                    Some(TemplateKind::External) => component
+                        .template_path
+                        .as_ref()
+                        .and_then(|template_path| Some((fs::read_to_string(template_path).ok()?, template_path.clone()))),
+                    Some(TemplateKind::Inline) => component
+                        .inline_template
+                        .as_ref()
+                        .map(|content| (content.clone(), component.path.clone())),
+                    None => None,
+                };
+
+                let mut edges = Vec::new();
+                if let Some((template, template_file)) = template {
+                    for element in template::parse_elements(&template) {
+                        for (name, kind, selector_text, selector) in &selectables {
+                            if selector.matches(&element) {
+                                edges.push(RenderEdge {
+                                    parent: component.name.clone(),
+                                    child: (*name).clone(),
+                                    kind: *kind,
+                                    selector: (*selector_text).clone(),
+                                    template: template_file.clone(),
+                                    line: element.line,
+                                });
+                            }
+                        }
+                    }
                     progress.inc(1);
                 }
+                edges
+            })
+            .collect();
+
+        progress.finish_with_message(format!("Found {} template usages", edges.len()));
+
+        let roots = build_render_roots(&components, &edges);
+
+        Ok(RenderTree { roots, edges })
+    }
+
+    pub fn analyze_injection(&mut self) -> Result<InjectionGraph> {
+        self.info("Analyzing dependency injection...");
+
+        let progress = self.create_progress_bar("Scanning for injection sites");
+
+        let is_source = |file: &IndexedFile| {
+            let file_name = file.path.file_name().and_then(|f| f.to_str()).unwrap_or("");
+            file_name.ends_with(".ts") && !file_name.ends_with(".spec.ts") && !file_name.ends_with(".d.ts")
+        };
+        let sources = self.parse_files(&progress, is_source, |path| Ok((path.to_path_buf(), self.read_source(path)?)))?;
+
+        let resolver = ModuleResolver::new(&self.root_path);
+
+        // Classes and InjectionTokens declared in the project, by name
+        let mut declared: HashMap<&str, PathBuf> = HashMap::new();
+        let mut injection_tokens = Vec::new();
+
+        for (path, source) in &sources {
+            for class in &source.classes {
+                declared.entry(class.name.as_str()).or_insert_with(|| resolver.relative_path(path));
+            }
+            for variable in &source.variables {
+                if let Some(Expr::New { callee, type_args, args }) = &variable.initializer
+                    && callee.path().as_deref() == Some("InjectionToken")
+                {
+                    declared.entry(variable.name.as_str()).or_insert_with(|| resolver.relative_path(path));
+                    injection_tokens.push(InjectionTokenDecl {
+                        name: variable.name.clone(),
+                        path: resolver.relative_path(path),
+                        type_name: type_args.clone(),
+                        description: args.first().and_then(Expr::as_str).map(str::to_string),
+                        consumers: Vec::new(),
+                    });
+                }
             }
         }
 
+        // Every parsed file by project-relative path, for following barrel
+        // re-exports to the file a token is declared in
+        let files: HashMap<PathBuf, (&Path, &SourceFile)> = sources
+            .iter()
+            .map(|(path, source)| (resolver.relative_path(path), (path.as_path(), source.as_ref())))
+            .collect();
+
+        let mut edges = Vec::new();
+        // The declared name of each edge's token, as imports may alias it
+        let mut declared_names = Vec::new();
+
+        for (path, source) in &sources {
+            let file_kind = consumer_kind_for_file(path);
+
+            let consumer_path = resolver.relative_path(path);
+
+            for class in &source.classes {
+                let Some(kind) = consumer_kind_for_class(class) else { continue };
+                let kind = file_kind.filter(|_| kind == ConsumerKind::Service).unwrap_or(kind);
+
+                for (token, site, optional) in class_injections(class) {
+                    let (provider, declared_name) = resolve_token(source, path, &token, &declared, &files, &resolver);
+                    declared_names.push(declared_name);
+                    edges.push(InjectionEdge {
+                        consumer: class.name.clone(),
+                        consumer_kind: kind,
+                        consumer_path: consumer_path.clone(),
+                        token,
+                        site,
+                        optional,
+                        provider,
+                    });
+                }
+            }
+
+            // Functional guards, resolvers and interceptors
+            let functions = source.functions
+                .iter()
+                .map(|f| (&f.name, &f.body))
+                .chain(
+                    source.variables
+                        .iter()
+                        .filter_map(|v| v.initializer.as_ref().map(|init| (&v.name, init)))
+                        .filter(|(_, init)| matches!(init, Expr::Arrow { .. }))
+                );
+
+            for (name, body) in functions {
+                for (token, optional) in inject_calls(body) {
+                    let (provider, declared_name) = resolve_token(source, path, &token, &declared, &files, &resolver);
+                    declared_names.push(declared_name);
+                    edges.push(InjectionEdge {
+                        consumer: name.clone(),
+                        consumer_kind: file_kind.unwrap_or(ConsumerKind::Function),
+                        consumer_path: consumer_path.clone(),
+                        token,
+                        site: InjectionSite::InjectFunction,
+                        optional,
+                        provider,
+                    });
+                }
+            }
+        }
+
+        for token in &mut injection_tokens {
+            let mut consumers: Vec<String> = edges
+                .iter()
+                .zip(&declared_names)
+                .filter(|(e, name)| **name == token.name && e.provider == TokenSource::Project(token.path.clone()))
+                .map(|(e, _)| e.consumer.clone())
+                .collect();
+            consumers.sort();
+            consumers.dedup();
+            token.consumers = consumers;
+        }
+
+        let mut unresolved: Vec<String> = edges
+            .iter()
+            .filter(|e| e.provider == TokenSource::Unresolved)
+            .map(|e| e.token.clone())
+            .collect();
+        unresolved.sort();
+        unresolved.dedup();
+
+        progress.finish_with_message(format!("Found {} injections", edges.len()));
+
+        Ok(InjectionGraph {
+            edges,
+            unresolved,
+            injection_tokens,
+        })
+    }
+
+    pub fn analyze_services(&mut self) -> Result<Vec<Service>> {
+        self.info("Analyzing services...");
+
+        let progress = self.create_progress_bar("Scanning for services");
+
+        // Parse all service files
+        let services = self.parse_files(&progress, |file| file.file_type == FileType::Service, |path| self.parse_service(path))?;
+
         progress.finish_with_message(format!("Found {} services", services.len()));
 
         Ok(services)
     }
 
     pub fn analyze_modules(&mut self) -> Result<Vec<Module>> {
-        println!("{} Analyzing modules...", "INFO:".blue().bold());
+        self.info("Analyzing modules...");
 
-        let mut modules = Vec::new();
         let progress = self.create_progress_bar("Scanning for modules");
 
-        // Find all module files
-        for entry in self.walk_project_files() {
-            let path = entry.path();
-            if let Some(file_name) = path.file_name().and_then(|f| f.to_str()) {
-                if file_name.ends_with(".module.ts") {
-                    let module = self.parse_module(path)?;
-                    modules.push(module);
-                    progress.inc(1);
-                }
-            }
-        }
+        // Parse all module files
+        let modules = self.parse_files(&progress, |file| file.file_type == FileType::Module, |path| self.parse_module(path))?;
 
         progress.finish_with_message(format!("Found {} modules", modules.len()));
 
@@ -125,244 +536,392 @@ impl Analyzer {
     }
 
     pub fn analyze_dependencies(&mut self) -> Result<Vec<Dependency>> {
-        println!("{} Analyzing dependencies...", "INFO:".blue().bold());
+        self.info("Analyzing dependencies...");
 
-        let mut dependencies = Vec::new();
         let progress = self.create_progress_bar("Scanning for dependencies");
+        let resolver = ModuleResolver::new(&self.root_path);
 
-        // Find all TypeScript files
-        for entry in self.walk_project_files() {
-            let path = entry.path();
-            if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
-                if extension == "ts" {
-                    let file_deps = self.parse_file_dependencies(path)?;
-                    dependencies.extend(file_deps);
-                    progress.inc(1);
-                }
-            }
-        }
+        // Parse all TypeScript files
+        let is_typescript = |file: &IndexedFile| file.path.extension().is_some_and(|e| e == "ts");
+        let dependencies: Vec<Dependency> = self
+            .parse_files(&progress, is_typescript, |path| self.parse_file_dependencies(path, &resolver))?
+            .into_iter()
+            .flatten()
+            .collect();
 
         progress.finish_with_message(format!("Found {} dependencies", dependencies.len()));
 
         Ok(dependencies)
     }
 
+    pub fn analyze_cycles(&mut self) -> Result<Vec<Cycle>> {
+        let dependencies = self.analyze_dependencies()?;
+
+        self.info("Detecting circular dependencies...");
+        let cycles = cycles::find_cycles(&dependencies);
+
+        Ok(cycles)
+    }
+
     pub fn analyze_routes(&mut self) -> Result<Vec<Route>> {
-        println!("{} Analyzing routes...", "INFO:".blue().bold());
+        self.info("Analyzing routes...");
+
+        let progress = self.create_progress_bar("Scanning for route definitions");
+        let resolver = ModuleResolver::new(&self.root_path);
+
+        // Routing modules, standalone `*.routes.ts` files, and any other file
+        // that hands routes to the router
+        let is_source = |file: &IndexedFile| {
+            let file_name = file.path.file_name().and_then(|f| f.to_str()).unwrap_or("");
+            file_name.ends_with(".ts") && !file_name.ends_with(".spec.ts")
+        };
+        let parsed = self.parse_files(&progress, is_source, |path| {
+            let file_name = path.file_name().and_then(|f| f.to_str()).unwrap_or("");
+            let is_routing_file =
+                (file_name.contains("routing") && file_name.ends_with(".module.ts")) ||
+                file_name.ends_with(".routes.ts");
+            if !is_routing_file && !self.might_configure_router(path) {
+                return Ok(None);
+            }
 
+            let arrays = self.parse_routes(path, &resolver)?;
+            Ok((!arrays.is_empty()).then(|| (resolver.relative_path(path), arrays)))
+        })?;
+        let route_files: BTreeMap<PathBuf, Vec<RouteArray>> = parsed.into_iter().flatten().collect();
+
+        // Stitch every route array, then keep as roots only the arrays that
+        // no `loadChildren` pulls in
+        let mut loaded = HashSet::new();
         let mut routes = Vec::new();
-        let progress = self.create_progress_bar("Scanning for routing modules");
-
-        // Find all routing module files
-        for entry in self.walk_project_files() {
-            let path = entry.path();
-            if let Some(file_name) = path.file_name().and_then(|f| f.to_str()) {
-                if file_name.contains("routing") && file_name.ends_with(".module.ts") {
-                    let module_routes = self.parse_routes(path)?;
-                    routes.extend(module_routes);
-                    progress.inc(1);
-                }
+        for (file, arrays) in &route_files {
+            for array in arrays {
+                let mut array_routes = array.routes.clone();
+                self.stitch_routes(&mut array_routes, "", &route_files, &resolver, &mut Vec::new(), &mut loaded);
+                routes.push((file, array_routes));
             }
         }
+        let routes: Vec<Route> = routes
+            .into_iter()
+            .filter(|(file, _)| !loaded.contains(*file))
+            .flat_map(|(_, array_routes)| array_routes)
+            .collect();
 
-        progress.finish_with_message(format!("Found {} routes", routes.len()));
+        progress.finish_with_message(format!("Found {} top-level routes", routes.len()));
 
         Ok(routes)
     }
 
-    // Private helper methods
-
-    fn scan_directory(
-        &self,
-        dir_path: &Path,
-        parent_node: &mut DirectoryNode,
-        depth: usize
-    ) -> Result<()> {
-        if depth >= self.max_depth {
-            return Ok(());
-        }
-
-        // Skip excluded directories
-        if
-            !self.include_node_modules &&
-            dir_path.file_name().and_then(|n| n.to_str()) == Some("node_modules")
-        {
-            return Ok(());
-        }
-
-        if let Some(name) = dir_path.file_name().and_then(|n| n.to_str()) {
-            if name == ".angular" || name == ".vscode" || name == ".git" {
-                return Ok(());
-            }
-        }
+    // Every analysis in one run over the file index. Each file is read and
+    // parsed once, however many of the analyses look at it.
+    pub fn analyze_all(&mut self) -> Result<ProjectModel> {
+        let mut model = ProjectModel {
+            structure: self.analyze_structure()?,
+            components: self.analyze_components()?,
+            directives: self.analyze_directives()?,
+            pipes: self.analyze_pipes()?,
+            services: self.analyze_services()?,
+            modules: self.analyze_modules()?,
+            routes: self.analyze_routes()?,
+            dependencies: self.analyze_dependencies()?,
+            links: Vec::new(),
+        };
 
-        let entries = fs
-            ::read_dir(dir_path)
-            .context(format!("Failed to read directory '{}'", dir_path.display()))?;
+        self.info("Linking classes...");
+        let resolver = ModuleResolver::new(&self.root_path);
+        model.links = links::link_classes(&model, &resolver);
 
-        for entry in entries {
-            let entry = entry.context(format!("Failed to read entry in '{}'", dir_path.display()))?;
-            let path = entry.path();
+        Ok(model)
+    }
 
-            if path.is_dir() {
-                let dir_name = path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("unknown")
-                    .to_string();
+    pub fn analyze_boundaries(&mut self) -> Result<Vec<BoundaryViolation>> {
+        let dependencies = self.analyze_dependencies()?;
 
-                let mut dir_node = DirectoryNode {
-                    name: dir_name,
-                    path: path.clone(),
-                    directories: Vec::new(),
-                    files: Vec::new(),
-                };
+        self.info("Checking project boundaries...");
+        let Some(workspace) = self.workspace() else {
+            return Ok(Vec::new());
+        };
+        let constraints = boundaries::load_constraints(&self.root_path);
+        let violations = boundaries::check_boundaries(&dependencies, workspace, &constraints);
 
-                self.scan_directory(&path, &mut dir_node, depth + 1)?;
+        Ok(violations)
+    }
 
-                // Only add the directory if it contains files or other directories
-                if !dir_node.files.is_empty() || !dir_node.directories.is_empty() {
-                    parent_node.directories.push(dir_node);
-                }
-            } else {
-                if let Some(file_node) = self.create_file_node(&path)? {
-                    parent_node.files.push(file_node);
-                }
-            }
+    pub fn check_architecture(&mut self) -> Result<Vec<ArchitectureViolation>> {
+        if self.config.layers.is_empty() {
+            anyhow::bail!("No layers configured; declare [[layers]] in {}", config::CONFIG_FILE);
         }
 
-        // Sort directories and files by name
-        parent_node.directories.sort_by(|a, b| a.name.cmp(&b.name));
-        parent_node.files.sort_by(|a, b| a.name.cmp(&b.name));
+        let dependencies = self.analyze_dependencies()?;
 
-        Ok(())
+        self.info("Checking architecture rules...");
+        let prefix = self.config.root_prefix(&self.root_path);
+        architecture::check_architecture(&dependencies, &self.config.layers, &self.config.rules, &prefix)
     }
 
-    fn create_file_node(&self, path: &Path) -> Result<Option<FileNode>> {
-        let file_name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown")
-            .to_string();
+    pub fn audit_routes(&mut self) -> Result<Vec<RouteFinding>> {
+        let routes = self.analyze_routes()?;
+        let components = self.analyze_components()?;
 
-        // Skip excluded files
-        if !self.include_tests && file_name.contains(".spec.") {
-            return Ok(None);
-        }
+        self.info("Auditing routes...");
+        let resolver = ModuleResolver::new(&self.root_path);
+        let findings = route_audit::audit_routes(&self.root_path, &routes, &components, &resolver);
 
-        if !self.include_styles && is_style_file(path) {
-            return Ok(None);
-        }
+        Ok(findings)
+    }
 
-        let file_type = determine_file_type(path);
+    // Private helper methods
 
-        Ok(
-            Some(FileNode {
-                name: file_name,
-                path: path.to_path_buf(),
-                file_type,
-            })
-        )
+    fn file_index(&self) -> &FileIndex {
+        self.index.get_or_init(|| scanner::scan(&self.root_path, &self.options))
     }
 
-    fn walk_project_files(&self) -> impl Iterator<Item = DirEntry> {
-        WalkDir::new(&self.root_path)
-            .follow_links(true)
-            .into_iter()
-            .filter_entry(move |e| self.is_included_entry(e))
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_type().is_file())
+    fn workspace(&self) -> Option<&Workspace> {
+        self.workspace
+            .get_or_init(|| Workspace::load(&self.root_path, &self.file_index().project_json_files))
+            .as_ref()
     }
 
-    fn is_included_entry(&self, entry: &DirEntry) -> bool {
-        let path = entry.path();
+    // Parses the project files `wanted` picks on the thread pool. Results
+    // keep the sorted order of the file index.
+    fn parse_files<T, F>(&self, progress: &ProgressBar, wanted: impl Fn(&IndexedFile) -> bool, parse: F) -> Result<Vec<T>>
+    where
+        T: Send,
+        F: Fn(&Path) -> Result<T> + Sync,
+    {
+        let files: Vec<&IndexedFile> = self.project_files().filter(|file| wanted(file)).collect();
+        let results: Vec<Result<T>> = files
+            .par_iter()
+            .map(|file| {
+                let result = parse(&file.path);
+                progress.inc(1);
+                result
+            })
+            .collect();
 
-        // Skip node_modules if not included
-        if !self.include_node_modules {
-            let is_node_modules = path.components().any(|c| {
-                if let std::path::Component::Normal(os_str) = c {
-                    match os_str.to_str() {
-                        Some("node_modules") | Some(".angular") | Some(".vscode") | Some(".git") => true,
-                        _ => false,
-                    }
-                } else {
-                    false
-                }
-            });
+        results.into_iter().collect()
+    }
 
-            if is_node_modules {
-                return false;
-            }
-        }
+    // The indexed files of the selected project
+    fn project_files(&self) -> impl Iterator<Item = &IndexedFile> {
+        self.file_index().files.iter().filter(|file| self.in_selected_project(&file.path))
+    }
 
-        // Skip test files if not included
-        if !self.include_tests {
-            if let Some(file_name) = path.file_name().and_then(|f| f.to_str()) {
-                if file_name.contains(".spec.") {
-                    return false;
-                }
-            }
+    // The workspace project a file belongs to
+    fn project_of(&self, path: &Path) -> Option<String> {
+        let relative = self.relative_path(path);
+        self.workspace()?.project_for(relative).map(|p| p.name.clone())
+    }
+
+    fn project_info(&self, path: &Path) -> ProjectInfo {
+        let relative = self.relative_path(path);
+        match self.workspace().and_then(|w| w.project_for(relative)) {
+            Some(project) => ProjectInfo { project: Some(project.name.clone()), tags: project.tags.clone() },
+            None => ProjectInfo::default(),
         }
+    }
 
-        // Skip style files if not included
-        if !self.include_styles && is_style_file(path) {
-            return false;
+    fn in_selected_project(&self, path: &Path) -> bool {
+        match self.project {
+            Some(ref project) => self.project_of(path).as_deref() == Some(project.as_str()),
+            None => true,
         }
+    }
 
-        true
+    fn relative_path<'p>(&self, path: &'p Path) -> &'p Path {
+        path.strip_prefix(&self.root_path).unwrap_or(path)
     }
 
-    fn parse_component(&self, path: &Path) -> Result<Component> {
+    fn read_source(&self, path: &Path) -> Result<Arc<SourceFile>> {
+        if let Some(source) = self.sources.lock().unwrap().get(path) {
+            return Ok(Arc::clone(source));
+        }
+
         let content = fs
             ::read_to_string(path)
             .context(format!("Failed to read file '{}'", path.display()))?;
 
+        Ok(self.store_source(path, &content))
+    }
+
+    fn store_source(&self, path: &Path, content: &str) -> Arc<SourceFile> {
+        let source = Arc::new(match self.cache {
+            Some(ref cache) => cache.parse(content),
+            None => typescript::parse(content),
+        });
+        self.sources.lock().unwrap().insert(path.to_path_buf(), Arc::clone(&source));
+        source
+    }
+
+    // Whether a file outside the usual routing file names hands routes to the
+    // router. A file parsed by an earlier analysis is checked on its parse;
+    // any other is read once and parsed only when its text mentions the
+    // router.
+    fn might_configure_router(&self, path: &Path) -> bool {
+        if let Some(source) = self.sources.lock().unwrap().get(path) {
+            return !router_call_arguments(source).is_empty();
+        }
+
+        match fs::read_to_string(path) {
+            Ok(content) if mentions_router(&content) => {
+                self.store_source(path, &content);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_components(&self, path: &Path, standalone_by_default: bool) -> Result<Vec<Component>> {
+        let source = self.read_source(path)?;
+
         let file_stem = path
             .file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or("")
             .replace(".component", "");
 
-        let component_name = extract_class_name(&content).unwrap_or_else(||
-            format!("{}Component", pascal_case(&file_stem))
-        );
+        let parent_dir = path.parent().unwrap_or(Path::new(""));
+        let test_path = find_related_file(parent_dir, &file_stem, "component.spec.ts");
 
-        let selector = extract_selector(&content);
+        let mut components: Vec<Component> = source
+            .decorated_classes("Component")
+            .map(|class| {
+                let metadata = class.decorator("Component").and_then(|d| d.metadata());
+                let property = |name: &str| {
+                    metadata.map(|m| extract_array_property(&source, m, name)).unwrap_or_default()
+                };
 
-        let parent_dir = path.parent().unwrap_or(Path::new(""));
+                let standalone = match metadata.and_then(|m| m.get("standalone")) {
+                    Some(Expr::Ident(value)) => value == "true",
+                    _ => standalone_by_default,
+                };
 
-        // Find related files
-        let template_path = find_related_file(parent_dir, &file_stem, "component.html");
-        let test_path = find_related_file(parent_dir, &file_stem, "component.spec.ts");
+                let mut component = Component {
+                    name: class.name.clone(),
+                    selector: metadata.and_then(|m| m.get_str("selector")).map(str::to_string),
+                    path: path.to_path_buf(),
+                    template_kind: None,
+                    template_path: None,
+                    inline_template: None,
+                    style_paths: Vec::new(),
+                    inline_styles: 0,
+                    test_path: test_path.clone(),
+                    standalone,
+                    imports: property("imports"),
+                    providers: property("providers"),
+                    host_directives: property("hostDirectives"),
+                    schemas: property("schemas"),
+                    api: extract_component_api(class, metadata),
+                    project_info: self.project_info(path),
+                };
 
-        // Find style files
-        let mut style_paths = Vec::new();
-        for extension in &["css", "scss", "sass", "less"] {
-            if
-                let Some(style_path) = find_related_file(
-                    parent_dir,
-                    &file_stem,
-                    &format!("component.{}", extension)
-                )
-            {
-                style_paths.push(style_path);
+                if let Some(metadata) = metadata {
+                    read_template_and_styles(&source, metadata, parent_dir, &mut component);
+                }
+
+                component
+            })
+            .collect();
+
+        // A `.component.ts` file without a decorated class is still listed,
+        // with its template and styles found by naming convention
+        if components.is_empty() {
+            let component_name = source
+                .first_exported_class()
+                .map(|c| c.name.clone())
+                .unwrap_or_else(|| format!("{}Component", pascal_case(&file_stem)));
+
+            let template_path = find_related_file(parent_dir, &file_stem, "component.html");
+
+            let mut style_paths = Vec::new();
+            for extension in &["css", "scss", "sass", "less"] {
+                if
+                    let Some(style_path) = find_related_file(
+                        parent_dir,
+                        &file_stem,
+                        &format!("component.{}", extension)
+                    )
+                {
+                    style_paths.push(style_path);
+                }
             }
+
+            components.push(Component {
+                name: component_name,
+                selector: None,
+                path: path.to_path_buf(),
+                template_kind: template_path.as_ref().map(|_| TemplateKind::External),
+                template_path,
+                inline_template: None,
+                style_paths,
+                inline_styles: 0,
+                test_path,
+                standalone: false,
+                imports: Vec::new(),
+                providers: Vec::new(),
+                host_directives: Vec::new(),
+                schemas: Vec::new(),
+                api: Vec::new(),
+                project_info: self.project_info(path),
+            });
         }
 
-        Ok(Component {
-            name: component_name,
-            selector,
-            path: path.to_path_buf(),
-            template_path,
-            style_paths,
-            test_path,
-        })
+        Ok(components)
+    }
+
+    fn parse_directives(&self, path: &Path, standalone_by_default: bool) -> Result<Vec<Directive>> {
+        let source = self.read_source(path)?;
+
+        let directives = source
+            .decorated_classes("Directive")
+            .map(|class| {
+                let metadata = class.decorator("Directive").and_then(|d| d.metadata());
+
+                let standalone = match metadata.and_then(|m| m.get("standalone")) {
+                    Some(Expr::Ident(value)) => value == "true",
+                    _ => standalone_by_default,
+                };
+
+                Directive {
+                    name: class.name.clone(),
+                    selector: metadata.and_then(|m| m.get_str("selector")).map(str::to_string),
+                    path: path.to_path_buf(),
+                    standalone,
+                    project_info: self.project_info(path),
+                }
+            })
+            .collect();
+
+        Ok(directives)
+    }
+
+    fn parse_pipes(&self, path: &Path, standalone_by_default: bool) -> Result<Vec<Pipe>> {
+        let source = self.read_source(path)?;
+
+        let pipes = source
+            .decorated_classes("Pipe")
+            .map(|class| {
+                let metadata = class.decorator("Pipe").and_then(|d| d.metadata());
+                let flag = |name: &str, default: bool| match metadata.and_then(|m| m.get(name)) {
+                    Some(Expr::Ident(value)) => value == "true",
+                    _ => default,
+                };
+
+                Pipe {
+                    name: class.name.clone(),
+                    pipe_name: metadata.and_then(|m| m.get_str("name")).map(str::to_string),
+                    path: path.to_path_buf(),
+                    standalone: flag("standalone", standalone_by_default),
+                    pure: flag("pure", true),
+                    project_info: self.project_info(path),
+                }
+            })
+            .collect();
+
+        Ok(pipes)
     }
 
     fn parse_service(&self, path: &Path) -> Result<Service> {
-        let content = fs
-            ::read_to_string(path)
-            .context(format!("Failed to read file '{}'", path.display()))?;
+        let source = self.read_source(path)?;
 
         let file_stem = path
             .file_stem()
@@ -370,11 +929,17 @@ impl Analyzer {
             .unwrap_or("")
             .replace(".service", "");
 
-        let service_name = extract_class_name(&content).unwrap_or_else(||
-            format!("{}Service", pascal_case(&file_stem))
-        );
+        let class = source.decorated_classes("Injectable").next().or_else(|| source.first_exported_class());
+
+        let service_name = class
+            .map(|c| c.name.clone())
+            .unwrap_or_else(|| format!("{}Service", pascal_case(&file_stem)));
 
-        let injectable_scope = extract_injectable_scope(&content);
+        let injectable_scope = class
+            .and_then(|c| c.decorator("Injectable"))
+            .and_then(|d| d.metadata())
+            .and_then(|m| m.get("providedIn"))
+            .and_then(|scope| scope.as_str().map(str::to_string).or_else(|| scope.path()));
 
         let parent_dir = path.parent().unwrap_or(Path::new(""));
         let test_path = find_related_file(parent_dir, &file_stem, "service.spec.ts");
@@ -384,13 +949,12 @@ impl Analyzer {
             path: path.to_path_buf(),
             injectable_scope,
             test_path,
+            project_info: self.project_info(path),
         })
     }
 
     fn parse_module(&self, path: &Path) -> Result<Module> {
-        let content = fs
-            ::read_to_string(path)
-            .context(format!("Failed to read file '{}'", path.display()))?;
+        let source = self.read_source(path)?;
 
         let file_stem = path
             .file_stem()
@@ -398,84 +962,254 @@ impl Analyzer {
             .unwrap_or("")
             .replace(".module", "");
 
-        let module_name = extract_class_name(&content).unwrap_or_else(||
-            format!("{}Module", pascal_case(&file_stem))
-        );
+        let class = source.decorated_classes("NgModule").next().or_else(|| source.first_exported_class());
+
+        let module_name = class
+            .map(|c| c.name.clone())
+            .unwrap_or_else(|| format!("{}Module", pascal_case(&file_stem)));
 
         // Extract module metadata
-        let declarations = extract_array_property(&content, "declarations");
-        let imports = extract_array_property(&content, "imports");
-        let exports = extract_array_property(&content, "exports");
-        let providers = extract_array_property(&content, "providers");
-        let bootstrap = extract_array_property(&content, "bootstrap");
+        let metadata = class.and_then(|c| c.decorator("NgModule")).and_then(|d| d.metadata());
+        let property = |name: &str| {
+            metadata.map(|m| extract_array_property(&source, m, name)).unwrap_or_default()
+        };
 
         Ok(Module {
             name: module_name,
             path: path.to_path_buf(),
-            declarations,
-            imports,
-            exports,
-            providers,
-            bootstrap,
+            declarations: property("declarations"),
+            imports: property("imports"),
+            exports: property("exports"),
+            providers: property("providers"),
+            bootstrap: property("bootstrap"),
+            project_info: self.project_info(path),
         })
     }
 
-    fn parse_file_dependencies(&self, path: &Path) -> Result<Vec<Dependency>> {
-        let content = fs
-            ::read_to_string(path)
-            .context(format!("Failed to read file '{}'", path.display()))?;
+    fn parse_file_dependencies(&self, path: &Path, resolver: &ModuleResolver) -> Result<Vec<Dependency>> {
+        let source = self.read_source(path)?;
 
         let mut dependencies = Vec::new();
+        let source_path = resolver.relative_path(path);
 
-        // Find import statements
-        let re = Regex::new(r#"import\s+\{([^}]+)\}\s+from\s+['"]([^'"]+)['"]"#).unwrap();
+        for import in &source.imports {
+            let target = resolver.resolve(path, &import.specifier);
 
-        for cap in re.captures_iter(&content) {
-            let imports = cap[1]
-                .split(',')
-                .map(|s| s.trim())
-                .filter(|s| !s.is_empty())
-                .collect::<Vec<_>>();
+            let mut names = Vec::new();
+            if let Some(ref default) = import.default {
+                names.push(ImportedName {
+                    name: "default".to_string(),
+                    alias: Some(default.clone()),
+                    type_only: import.type_only,
+                });
+            }
+            if let Some(ref namespace) = import.namespace {
+                names.push(ImportedName {
+                    name: "*".to_string(),
+                    alias: Some(namespace.clone()),
+                    type_only: import.type_only,
+                });
+            }
+            names.extend(import.named.iter().map(|specifier| ImportedName {
+                name: specifier.name.clone(),
+                alias: specifier.alias.clone(),
+                type_only: import.type_only || specifier.type_only,
+            }));
+
+            // `import { type A }` is erased just like `import type { A }`
+            let type_only = import.type_only || (!names.is_empty() && names.iter().all(|n| n.type_only));
+
+            // The first name that says what kind of Angular artifact it is
+            let import_type = names
+                .iter()
+                .map(|n| match (n.name.as_str(), n.alias.as_deref()) {
+                    ("default", Some(local)) => determine_import_type(local),
+                    (name, _) => determine_import_type(name),
+                })
+                .find(|t| *t != ImportType::Other)
+                .unwrap_or(ImportType::Other);
+
+            let kind = match import.kind {
+                ImportKind::Import => DependencyKind::Import,
+                ImportKind::ReExport => DependencyKind::ReExport,
+                ImportKind::Require => DependencyKind::Require,
+            };
+
+            dependencies.push(Dependency {
+                source: source_path.clone(),
+                specifier: import.specifier.clone(),
+                target,
+                import_type,
+                kind,
+                names,
+                type_only,
+                dynamic: import.dynamic,
+                line: import.line,
+            });
+        }
 
-            let source = cap[2].trim();
+        Ok(dependencies)
+    }
+
+    fn parse_routes(&self, path: &Path, resolver: &ModuleResolver) -> Result<Vec<RouteArray>> {
+        let source = self.read_source(path)?;
+        let file = resolver.relative_path(path);
+
+        let mut arrays = Vec::new();
+        let is_routes_file = path.to_string_lossy().ends_with(".routes.ts");
+
+        // Route arrays declared as `Routes`/`Route[]`, or default-exported
+        // from a `*.routes.ts` file for `loadChildren`
+        let mut route_arrays: Vec<(Option<&str>, &Expr)> = Vec::new();
+        let mut seen_variables = HashSet::new();
+        for variable in &source.variables {
+            let is_route_array = variable.type_annotation.as_deref().is_some_and(is_routes_type) ||
+                (variable.name == "default" && is_routes_file);
+            if is_route_array
+                && let Some(ref initializer) = variable.initializer
+            {
+                seen_variables.insert(variable.name.as_str());
+                route_arrays.push((Some(&variable.name), initializer));
+            }
+        }
 
-            for import in imports {
-                let import_type = determine_import_type(import);
+        // Routes passed straight to `provideRouter`, `RouterModule.forRoot`
+        // or `RouterModule.forChild`
+        for argument in router_call_arguments(&source) {
+            let name = match argument {
+                Expr::Ident(name) if !seen_variables.insert(name.as_str()) => continue,
+                Expr::Ident(name) => Some(name.as_str()),
+                _ => None,
+            };
+            route_arrays.push((name, argument));
+        }
 
-                dependencies.push(Dependency {
-                    source: path.to_path_buf(),
-                    target: source.to_string(),
-                    import_type,
-                });
+        let project_info = self.project_info(path);
+        for (name, routes_expr) in route_arrays {
+            if let Some(items) = source.resolve_local(routes_expr).as_array() {
+                let mut routes: Vec<Route> = items
+                    .iter()
+                    .filter_map(|item| parse_route_object(&source, item, &file))
+                    .collect();
+                set_route_project(&mut routes, &project_info);
+                if !routes.is_empty() {
+                    arrays.push(RouteArray {
+                        name: name.map(str::to_string),
+                        routes,
+                    });
+                }
             }
         }
 
-        Ok(dependencies)
+        Ok(arrays)
     }
 
-    fn parse_routes(&self, path: &Path) -> Result<Vec<Route>> {
-        let content = fs
-            ::read_to_string(path)
-            .context(format!("Failed to read file '{}'", path.display()))?;
+    // Fills in full paths and splices the routes each `loadChildren` points at
+    // under the route that loads them. `loaded` collects the files pulled in
+    // this way; `visiting` guards against files that load each other.
+    fn stitch_routes(
+        &self,
+        routes: &mut [Route],
+        parent_path: &str,
+        route_files: &BTreeMap<PathBuf, Vec<RouteArray>>,
+        resolver: &ModuleResolver,
+        visiting: &mut Vec<PathBuf>,
+        loaded: &mut HashSet<PathBuf>
+    ) {
+        for route in routes {
+            route.full_path = join_route_path(parent_path, &route.path);
+            self.stitch_routes(&mut route.children, &route.full_path, route_files, resolver, visiting, loaded);
+
+            let Some(ref lazy_module) = route.lazy_module else { continue };
+            let (specifier, export) = match lazy_module.split_once('#') {
+                Some((specifier, export)) => (specifier, Some(export)),
+                None => (lazy_module.as_str(), None),
+            };
+            let DependencyTarget::File(target) = resolver.resolve(&self.root_path.join(&route.file), specifier) else {
+                continue;
+            };
+            route.lazy_file = Some(target.clone());
+
+            if visiting.contains(&target) {
+                continue;
+            }
 
-        let mut routes = Vec::new();
+            let (files, mut lazy_routes) = self.lazy_routes(&target, export, route_files, resolver);
+            visiting.push(target);
+            self.stitch_routes(&mut lazy_routes, &route.full_path, route_files, resolver, visiting, loaded);
+            visiting.pop();
 
-        // Find routes array
-        if let Some(routes_content) = extract_routes_array(&content) {
-            // Parse individual routes
-            let route_objects = parse_route_objects(&routes_content);
+            loaded.extend(files);
+            route.children.extend(lazy_routes);
+        }
+    }
 
-            for route_obj in route_objects {
-                if let Some(route) = parse_route_object(&route_obj) {
-                    routes.push(route);
-                }
+    // The routes a `loadChildren` target provides, and the files they come
+    // from: a routes file (by export name, or its default export), or an
+    // NgModule whose imported routing module declares them
+    fn lazy_routes(
+        &self,
+        target: &Path,
+        export: Option<&str>,
+        route_files: &BTreeMap<PathBuf, Vec<RouteArray>>,
+        resolver: &ModuleResolver
+    ) -> (Vec<PathBuf>, Vec<Route>) {
+        if let Some(arrays) = route_files.get(target) {
+            let wanted = export.unwrap_or("default");
+            let routes = match arrays.iter().find(|a| a.name.as_deref() == Some(wanted)) {
+                Some(array) => array.routes.clone(),
+                None => arrays.iter().flat_map(|a| a.routes.clone()).collect(),
+            };
+            return (vec![target.to_path_buf()], routes);
+        }
+
+        let target_path = self.root_path.join(target);
+        let Ok(source) = self.read_source(&target_path) else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let mut files = Vec::new();
+        let mut routes = Vec::new();
+        for import in &source.imports {
+            if let DependencyTarget::File(imported) = resolver.resolve(&target_path, &import.specifier)
+                && let Some(arrays) = route_files.get(&imported)
+            {
+                routes.extend(arrays.iter().flat_map(|a| a.routes.clone()));
+                files.push(imported);
             }
         }
 
-        Ok(routes)
+        (files, routes)
+    }
+
+    fn angular_major_version(&self) -> Option<u32> {
+        let content = fs::read_to_string(self.root_path.join("package.json")).ok()?;
+        let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+
+        let version = ["dependencies", "devDependencies", "peerDependencies"]
+            .iter()
+            .find_map(|section| package[section]["@angular/core"].as_str())?;
+
+        // "^19.2.0", "~17.3.1", ">=18.0.0"
+        let digits: String = version
+            .trim_start_matches(|c: char| !c.is_ascii_digit())
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        digits.parse().ok()
+    }
+
+    fn info(&self, message: &str) {
+        if self.show_progress {
+            eprintln!("{} {}", "INFO:".blue().bold(), message);
+        }
     }
 
     fn create_progress_bar(&self, message: &str) -> ProgressBar {
+        if !self.show_progress {
+            return ProgressBar::hidden();
+        }
+
         let pb = ProgressBar::new_spinner();
         pb.set_style(
             ProgressStyle::default_spinner()
@@ -491,130 +1225,44 @@ impl Analyzer {
 
 // Helper functions
 
-fn determine_file_type(path: &Path) -> FileType {
-    let file_name = path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("");
-
-    if file_name.ends_with(".action.ts") {
-        return FileType::NgRxAction;
-    } else if file_name.ends_with(".html") {
-        return FileType::Template;
-    } else if file_name.ends_with(".reducer.ts") {
-        return FileType::NgRxReducer;
-    } else if file_name.ends_with(".effects.ts") {
-        return FileType::NgRxEffect;
-    } else if file_name.ends_with(".selector.ts") {
-        return FileType::NgRxSelector;
-    } else if file_name.ends_with(".ngrx.ts") {
-        return FileType::NgRxOther;
-    } else if file_name.ends_with(".component.ts") {
-        FileType::Component
-    } else if file_name.ends_with(".service.ts") {
-        FileType::Service
-    } else if file_name.ends_with(".module.ts") {
-        FileType::Module
-    } else if file_name.ends_with(".directive.ts") {
-        FileType::Directive
-    } else if file_name.ends_with(".pipe.ts") {
-        FileType::Pipe
-    } else if file_name.ends_with(".guard.ts") {
-        FileType::Guard
-    } else if file_name.ends_with(".resolver.ts") {
-        FileType::Resolver
-    } else if file_name.ends_with(".model.ts") || file_name.ends_with(".interface.ts") {
-        FileType::Model
-    } else if file_name == "tsconfig.json" || file_name == "angular.json" {
-        FileType::Config
-    } else if file_name.ends_with(".spec.ts") {
-        FileType::Test
-    } else if is_style_file(path) {
-        FileType::Style
-    } else {
-        FileType::Other
+fn sort_directory(node: &mut DirectoryNode) {
+    node.directories.sort_by(|a, b| a.name.cmp(&b.name));
+    node.files.sort_by(|a, b| a.name.cmp(&b.name));
+    for directory in &mut node.directories {
+        sort_directory(directory);
     }
 }
 
-fn is_style_file(path: &Path) -> bool {
-    let extension = path
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("");
-
-    matches!(extension, "css" | "scss" | "sass" | "less")
-}
-
-fn extract_class_name(content: &str) -> Option<String> {
-    let re = Regex::new(r"export\s+class\s+([A-Za-z0-9_]+)").ok()?;
-    re.captures(content).map(|cap| cap[1].to_string())
-}
-
-fn extract_selector(content: &str) -> Option<String> {
-    let re = Regex::new(r#"selector\s*:\s*['"]([^'"]+)['"]"#).ok()?;
-    re.captures(content).map(|cap| cap[1].to_string())
-}
+fn extract_array_property(source: &SourceFile, metadata: &ObjectLit, property_name: &str) -> Vec<String> {
+    let Some(value) = metadata.get(property_name) else {
+        return Vec::new();
+    };
 
-fn extract_injectable_scope(content: &str) -> Option<String> {
-    let re = Regex::new(r#"providedIn\s*:\s*['"]([^'"]+)['"]"#).ok()?;
-    re.captures(content).map(|cap| cap[1].to_string())
-}
+    // `imports: SHARED_IMPORTS` refers to an array declared in the same file
+    let Some(items) = source.resolve_local(value).as_array() else {
+        return value.symbol().into_iter().collect();
+    };
 
-fn extract_array_property(content: &str, property_name: &str) -> Vec<String> {
     let mut result = Vec::new();
-
-    let re_pattern = format!(r"{}\s*:\s*\[(.*?)\]", property_name);
-    let re = Regex::new(&re_pattern).unwrap();
-
-    if let Some(cap) = re.captures(content) {
-        let items_str = &cap[1];
-
-        // Split by commas, ignoring commas inside nested brackets
-        let mut items = Vec::new();
-        let mut start = 0;
-        let mut bracket_count = 0;
-
-        for (i, c) in items_str.char_indices() {
-            match c {
-                '{' | '[' | '(' => {
-                    bracket_count += 1;
+    for item in items {
+        match item {
+            Expr::Spread(inner) => {
+                match source.resolve_local(inner).as_array() {
+                    Some(spread_items) => result.extend(spread_items.iter().filter_map(Expr::symbol)),
+                    None => result.extend(inner.symbol()),
                 }
-                '}' | ']' | ')' => {
-                    bracket_count -= 1;
-                }
-                ',' if bracket_count == 0 => {
-                    items.push(&items_str[start..i]);
-                    start = i + 1;
-                }
-                _ => {}
             }
-        }
-
-        // Add the last item
-        if start < items_str.len() {
-            items.push(&items_str[start..]);
-        }
-
-        for item in items {
-            let item = item.trim();
-            if !item.is_empty() {
-                // Extract class names from the items
-                if let Some(class_name) = extract_item_name(item) {
-                    result.push(class_name);
-                }
+            // `{ provide: TOKEN, useClass: Impl }` and `{ directive: Foo, inputs: [...] }`
+            Expr::Object(obj) => {
+                result.extend(obj.get("provide").or_else(|| obj.get("directive")).and_then(Expr::symbol))
             }
+            _ => result.extend(item.symbol()),
         }
     }
 
     result
 }
 
-fn extract_item_name(item: &str) -> Option<String> {
-    // Try to extract class name (SimpleClass)
-    let re = Regex::new(r"([A-Za-z0-9_]+)").ok()?;
-    re.captures(item).map(|cap| cap[1].to_string())
-}
-
 fn determine_import_type(import: &str) -> ImportType {
     if import.ends_with("Component") {
         ImportType::Component
@@ -637,79 +1285,623 @@ fn determine_import_type(import: &str) -> ImportType {
     }
 }
 
-fn extract_routes_array(content: &str) -> Option<String> {
-    // Find the routes array
-    let re = Regex::new(r"const\s+routes\s*:\s*Routes\s*=\s*\[([\s\S]*?)\];").ok()?;
-    re.captures(content).map(|cap| cap[1].trim().to_string())
+// Reads `templateUrl`/`template` and `styleUrl`/`styleUrls`/`styles` from
+// component metadata, resolving URLs relative to the component file.
+fn read_template_and_styles(
+    source: &SourceFile,
+    metadata: &ObjectLit,
+    component_dir: &Path,
+    component: &mut Component
+) {
+    if let Some(url) = metadata.get_str("templateUrl") {
+        component.template_kind = Some(TemplateKind::External);
+        component.template_path = Some(fs_utils::normalize_path(&component_dir.join(url)));
+    } else if let Some(template) = metadata.get("template").map(|t| source.resolve_local(t)) {
+        component.template_kind = Some(TemplateKind::Inline);
+        component.inline_template = template.as_str().map(str::to_string);
+    }
+
+    let mut style_urls = Vec::new();
+    if let Some(url) = metadata.get_str("styleUrl") {
+        style_urls.push(url);
+    }
+    if let Some(urls) = metadata.get("styleUrls").map(|u| source.resolve_local(u)).and_then(Expr::as_array) {
+        style_urls.extend(urls.iter().filter_map(Expr::as_str));
+    }
+    component.style_paths = style_urls
+        .into_iter()
+        .map(|url| fs_utils::normalize_path(&component_dir.join(url)))
+        .collect();
+
+    // `styles` is either a single string or an array of them
+    component.inline_styles = match metadata.get("styles").map(|s| source.resolve_local(s)) {
+        Some(Expr::Array(items)) => items.len(),
+        Some(_) => 1,
+        None => 0,
+    };
 }
 
-fn parse_route_objects(routes_content: &str) -> Vec<String> {
-    let mut routes = Vec::new();
-    let mut start = 0;
-    let mut bracket_count = 0;
-    let mut in_object = false;
+// Collects decorator-based (`@Input()`, `@Output()`, `@ViewChild()`, ...) and
+// signal-based (`input()`, `output()`, `model()`, `viewChild()`, ...) members,
+// plus the `inputs`/`outputs` arrays of the component metadata.
+fn extract_component_api(class: &ClassDecl, metadata: Option<&ObjectLit>) -> Vec<ApiMember> {
+    let mut api = Vec::new();
 
-    for (i, c) in routes_content.char_indices() {
-        match c {
-            '{' => {
-                if bracket_count == 0 {
-                    start = i;
-                    in_object = true;
-                }
-                bracket_count += 1;
+    for member in &class.members {
+        let decorated = member.decorators
+            .iter()
+            .find_map(|d| api_kind_for_decorator(&d.name).map(|kind| (d, kind)));
+
+        if let Some((decorator, kind)) = decorated {
+            // `@Input('alias')` or `@Input({ alias: 'x', required: true })`
+            let options = decorator.metadata();
+            let alias = match (kind, decorator.args.first()) {
+                (ApiKind::Input | ApiKind::Output, Some(Expr::Str(alias))) => Some(alias.clone()),
+                _ => options.and_then(|o| o.get_str("alias")).map(str::to_string),
+            };
+            let required = matches!(options.and_then(|o| o.get("required")), Some(Expr::Ident(v)) if v == "true");
+
+            // Queries name their target in the decorator: `@ViewChild(ChildComponent)`
+            let query_target = match kind {
+                ApiKind::Input | ApiKind::Output | ApiKind::Model => None,
+                _ => decorator.args.first().and_then(Expr::path),
+            };
+            let type_name = member.type_annotation
+                .clone()
+                .or(query_target)
+                .or_else(|| member.initializer.as_ref().and_then(constructed_type));
+
+            api.push(ApiMember {
+                name: member.name.clone(),
+                kind,
+                alias,
+                required,
+                type_name,
+                signal: false,
+            });
+            continue;
+        }
+
+        let Some(init @ Expr::Call { type_args, args, .. }) = &member.initializer else {
+            continue;
+        };
+        let Some(callee) = init.callee_path() else {
+            continue;
+        };
+        let function = callee.strip_suffix(".required").unwrap_or(&callee);
+        let Some(kind) = api_kind_for_signal(function) else {
+            continue;
+        };
+
+        // Options are the last object argument: `input(0, { alias: 'x' })`
+        let alias = args
+            .iter()
+            .rev()
+            .find_map(Expr::as_object)
+            .and_then(|o| o.get_str("alias"))
+            .map(str::to_string);
+
+        let query_target = match kind {
+            ApiKind::Input | ApiKind::Output | ApiKind::Model => None,
+            _ => args.first().and_then(Expr::path),
+        };
+
+        api.push(ApiMember {
+            name: member.name.clone(),
+            kind,
+            alias,
+            required: callee.ends_with(".required"),
+            type_name: type_args.clone().or(query_target),
+            signal: true,
+        });
+    }
+
+    // `inputs: ['value', 'label: caption']`
+    for (property, kind) in [("inputs", ApiKind::Input), ("outputs", ApiKind::Output)] {
+        let Some(items) = metadata.and_then(|m| m.get(property)).and_then(Expr::as_array) else {
+            continue;
+        };
+        for item in items.iter().filter_map(Expr::as_str) {
+            let (name, alias) = match item.split_once(':') {
+                Some((name, alias)) => (name.trim(), Some(alias.trim().to_string())),
+                None => (item.trim(), None),
+            };
+            api.push(ApiMember {
+                name: name.to_string(),
+                kind,
+                alias,
+                required: false,
+                type_name: None,
+                signal: false,
+            });
+        }
+    }
+
+    api
+}
+
+fn api_kind_for_decorator(name: &str) -> Option<ApiKind> {
+    match name {
+        "Input" => Some(ApiKind::Input),
+        "Output" => Some(ApiKind::Output),
+        "ViewChild" => Some(ApiKind::ViewChild),
+        "ViewChildren" => Some(ApiKind::ViewChildren),
+        "ContentChild" => Some(ApiKind::ContentChild),
+        "ContentChildren" => Some(ApiKind::ContentChildren),
+        _ => None,
+    }
+}
+
+fn api_kind_for_signal(function: &str) -> Option<ApiKind> {
+    match function {
+        "input" => Some(ApiKind::Input),
+        "output" | "outputFromObservable" => Some(ApiKind::Output),
+        "model" => Some(ApiKind::Model),
+        "viewChild" => Some(ApiKind::ViewChild),
+        "viewChildren" => Some(ApiKind::ViewChildren),
+        "contentChild" => Some(ApiKind::ContentChild),
+        "contentChildren" => Some(ApiKind::ContentChildren),
+        _ => None,
+    }
+}
+
+// `new EventEmitter<string>()` -> `EventEmitter<string>`
+fn constructed_type(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::New { callee, type_args, .. } => {
+            let name = callee.path()?;
+            Some(match type_args {
+                Some(args) => format!("{}<{}>", name, args),
+                None => name,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn consumer_kind_for_class(class: &ClassDecl) -> Option<ConsumerKind> {
+    class.decorators.iter().find_map(|d| {
+        match d.name.as_str() {
+            "Component" => Some(ConsumerKind::Component),
+            "Directive" => Some(ConsumerKind::Directive),
+            "Pipe" => Some(ConsumerKind::Pipe),
+            "Injectable" => Some(ConsumerKind::Service),
+            "NgModule" => Some(ConsumerKind::Module),
+            _ => None,
+        }
+    })
+}
+
+fn consumer_kind_for_file(path: &Path) -> Option<ConsumerKind> {
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("");
+
+    if file_name.ends_with(".guard.ts") {
+        Some(ConsumerKind::Guard)
+    } else if file_name.ends_with(".resolver.ts") {
+        Some(ConsumerKind::Resolver)
+    } else if file_name.ends_with(".interceptor.ts") {
+        Some(ConsumerKind::Interceptor)
+    } else {
+        None
+    }
+}
+
+// Constructor parameters and `inject()` calls in field initializers and the
+// constructor body, as (token, site, optional) triples.
+fn class_injections(class: &ClassDecl) -> Vec<(String, InjectionSite, bool)> {
+    let mut injections = Vec::new();
+
+    if let Some(constructor) = class.constructor() {
+        for param in &constructor.params {
+            // `@Inject(TOKEN)` overrides the parameter type
+            let token = param
+                .decorator("Inject")
+                .and_then(|d| d.args.first())
+                .and_then(token_name)
+                .or_else(|| param.type_annotation.as_deref().map(strip_type_args));
+
+            if let Some(token) = token {
+                let optional = param.decorator("Optional").is_some();
+                injections.push((token, InjectionSite::Constructor, optional));
             }
-            '}' => {
-                bracket_count -= 1;
-                if bracket_count == 0 && in_object {
-                    routes.push(routes_content[start..=i].to_string());
-                    in_object = false;
-                }
+        }
+    }
+
+    for member in &class.members {
+        for expr in member.initializer.iter().chain(member.body.iter()) {
+            for (token, optional) in inject_calls(expr) {
+                injections.push((token, InjectionSite::InjectFunction, optional));
             }
-            _ => {}
         }
     }
 
-    routes
+    injections
 }
 
-fn parse_route_object(route_obj: &str) -> Option<Route> {
-    // Extract path
-    let path_re = Regex::new(r#"path\s*:\s*['"]([^'"]+)['"]"#).ok()?;
-    let path = path_re
-        .captures(route_obj)
-        .map(|cap| cap[1].to_string())
-        .unwrap_or_else(|| "".to_string());
+// `inject(Token)`, `inject<T>(TOKEN, { optional: true })` anywhere inside `expr`
+fn inject_calls(expr: &Expr) -> Vec<(String, bool)> {
+    let mut calls = Vec::new();
+
+    expr.walk(
+        &mut (|e| {
+            if e.callee_path().as_deref() == Some("inject") {
+                let args = e.call_args();
+                if let Some(token) = args.first().and_then(token_name) {
+                    let optional = args
+                        .get(1)
+                        .and_then(Expr::as_object)
+                        .and_then(|o| o.get("optional"))
+                        .is_some_and(|v| matches!(v, Expr::Ident(value) if value == "true"));
+                    calls.push((token, optional));
+                }
+            }
+        })
+    );
 
-    // Extract component
-    let component_re = Regex::new(r"component\s*:\s*([A-Za-z0-9_]+)").ok()?;
-    let component = component_re.captures(route_obj).map(|cap| cap[1].to_string());
+    calls
+}
 
-    // Extract lazy loading module
-    let lazy_re = Regex::new(r#"loadChildren\s*:\s*['"](.*?)['"]"#).ok()?;
-    let lazy_module = lazy_re.captures(route_obj).map(|cap| cap[1].to_string());
+// `Foo`, `tokens.API_URL` or `forwardRef(() => Foo)`
+fn token_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Call { args, .. } if expr.callee_path().as_deref() == Some("forwardRef") => {
+            match args.first() {
+                Some(Expr::Arrow { body, .. }) => body.path(),
+                _ => None,
+            }
+        }
+        _ => expr.path(),
+    }
+}
 
-    // Extract children routes
-    let children_re = Regex::new(r"children\s*:\s*\[([\s\S]*?)\]").ok()?;
-    let children = if let Some(cap) = children_re.captures(route_obj) {
-        let children_content = cap[1].trim();
-        let child_objects = parse_route_objects(children_content);
+// `Store<AppState>` -> `Store`
+fn strip_type_args(type_name: &str) -> String {
+    type_name.split('<').next().unwrap_or(type_name).trim().to_string()
+}
 
-        child_objects
+// Works out where an injection token comes from: an import from a package,
+// a class or InjectionToken declared in the project, or nowhere we can see.
+// Also returns the name the token is declared under, which an aliased import
+// hides.
+fn resolve_token(
+    source: &SourceFile,
+    path: &Path,
+    token: &str,
+    declared: &HashMap<&str, PathBuf>,
+    files: &HashMap<PathBuf, (&Path, &SourceFile)>,
+    resolver: &ModuleResolver
+) -> (TokenSource, String) {
+    let local_name = token.split('.').next().unwrap_or(token);
+    let name = token.rsplit('.').next().unwrap_or(token);
+
+    // Re-exports and dynamic imports bind no local names
+    let import = source.imports.iter().find_map(|import| {
+        if import.kind != ImportKind::Import || import.dynamic {
+            return None;
+        }
+        if import.default.as_deref() == Some(local_name) || import.namespace.as_deref() == Some(local_name) {
+            return Some((import, name));
+        }
+        import.named
             .iter()
-            .filter_map(|child_obj| parse_route_object(child_obj))
-            .collect()
-    } else {
-        Vec::new()
-    };
+            .find(|n| n.alias.as_deref().unwrap_or(&n.name) == local_name)
+            .map(|n| (import, if local_name == token { n.name.as_str() } else { name }))
+    });
+
+    if let Some((import, imported)) = import {
+        match resolver.resolve(path, &import.specifier) {
+            DependencyTarget::File(file) => {
+                let (file, imported) = find_export(&file, imported, files, resolver, 0)
+                    .unwrap_or((file, imported.to_string()));
+                return (TokenSource::Project(file), imported);
+            }
+            DependencyTarget::Package(package) => {
+                return (TokenSource::Package(package), imported.to_string());
+            }
+            DependencyTarget::Unresolved(_) => {}
+        }
+    }
+
+    match declared.get(name) {
+        Some(path) => (TokenSource::Project(path.clone()), name.to_string()),
+        None => (TokenSource::Unresolved, name.to_string()),
+    }
+}
+
+// The file that declares `name` among the exports of `file`, following
+// `export *` and `export { } from` re-exports through barrels
+fn find_export(
+    file: &Path,
+    name: &str,
+    files: &HashMap<PathBuf, (&Path, &SourceFile)>,
+    resolver: &ModuleResolver,
+    depth: usize
+) -> Option<(PathBuf, String)> {
+    if depth > MAX_REEXPORT_DEPTH {
+        return None;
+    }
+    let (path, source) = files.get(file)?;
+
+    let declares = source.classes.iter().any(|c| c.name == name)
+        || source.variables.iter().any(|v| v.name == name)
+        || source.functions.iter().any(|f| f.name == name);
+    if declares {
+        return Some((file.to_path_buf(), name.to_string()));
+    }
+
+    for import in source.imports.iter().filter(|import| import.kind == ImportKind::ReExport) {
+        let original = import.named.iter().find_map(|n| match (n.name.as_str(), n.alias.as_deref()) {
+            ("*", None) => Some(name),
+            ("*", Some(_)) => None,
+            (original, alias) => (alias.unwrap_or(original) == name).then_some(original),
+        });
+        let Some(original) = original else { continue };
+
+        if let DependencyTarget::File(target) = resolver.resolve(path, &import.specifier)
+            && let Some(found) = find_export(&target, original, files, resolver, depth + 1)
+        {
+            return Some(found);
+        }
+    }
+
+    None
+}
+
+// Components that no template renders are the roots of the render tree.
+// When every component is rendered somewhere (a cycle), all of them are roots.
+fn build_render_roots(components: &[Component], edges: &[RenderEdge]) -> Vec<RenderNode> {
+    let mut children: BTreeMap<&str, Vec<(&str, RenderKind)>> = BTreeMap::new();
+    for edge in edges {
+        let entry = children.entry(edge.parent.as_str()).or_default();
+        if !entry.iter().any(|(name, _)| *name == edge.child) {
+            entry.push((edge.child.as_str(), edge.kind));
+        }
+    }
+
+    let rendered: HashSet<&str> = edges.iter().map(|e| e.child.as_str()).collect();
+    let mut root_names: Vec<&str> = components
+        .iter()
+        .map(|c| c.name.as_str())
+        .filter(|name| !rendered.contains(name))
+        .collect();
+    if root_names.is_empty() {
+        root_names = components.iter().map(|c| c.name.as_str()).collect();
+    }
+    root_names.sort();
+    root_names.dedup();
+
+    let mut ancestors = Vec::new();
+    root_names
+        .into_iter()
+        .map(|name| build_render_node(name, RenderKind::Component, &children, &mut ancestors))
+        .collect()
+}
+
+fn build_render_node<'a>(
+    name: &'a str,
+    kind: RenderKind,
+    children: &BTreeMap<&'a str, Vec<(&'a str, RenderKind)>>,
+    ancestors: &mut Vec<&'a str>
+) -> RenderNode {
+    if ancestors.contains(&name) {
+        return RenderNode {
+            name: name.to_string(),
+            kind,
+            children: Vec::new(),
+            recursive: true,
+        };
+    }
+
+    ancestors.push(name);
+    let child_nodes = children
+        .get(name)
+        .map(|list| {
+            list.iter()
+                .map(|(child, child_kind)| build_render_node(child, *child_kind, children, ancestors))
+                .collect()
+        })
+        .unwrap_or_default();
+    ancestors.pop();
+
+    RenderNode {
+        name: name.to_string(),
+        kind,
+        children: child_nodes,
+        recursive: false,
+    }
+}
+
+fn parse_route_object(source: &SourceFile, route_obj: &Expr, file: &Path) -> Option<Route> {
+    let route_obj = source.resolve_local(route_obj).as_object()?;
+
+    let path = route_obj.get_str("path").unwrap_or("").to_string();
+    let component = route_obj.get("component").and_then(Expr::path);
+    let lazy_module = route_obj.get("loadChildren").and_then(lazy_import_target);
+    let lazy_component = route_obj.get("loadComponent").and_then(lazy_import_target);
+
+    let resolve = route_obj
+        .get("resolve")
+        .map(|resolve| source.resolve_local(resolve))
+        .and_then(Expr::as_object)
+        .map(|resolve| resolve.props.iter().map(|p| (p.key.clone(), handler_name(&p.value))).collect())
+        .unwrap_or_default();
+
+    let children = route_obj
+        .get("children")
+        .map(|children| source.resolve_local(children))
+        .and_then(Expr::as_array)
+        .map(|items| items.iter().filter_map(|child| parse_route_object(source, child, file)).collect())
+        .unwrap_or_default();
 
     Some(Route {
         path,
+        full_path: String::new(),
         component,
         children,
         lazy_module,
+        lazy_component,
+        lazy_file: None,
+        file: file.to_path_buf(),
+        line: route_obj.line,
+        can_activate: route_handlers(source, route_obj, "canActivate"),
+        can_activate_child: route_handlers(source, route_obj, "canActivateChild"),
+        can_deactivate: route_handlers(source, route_obj, "canDeactivate"),
+        can_match: route_handlers(source, route_obj, "canMatch"),
+        resolve,
+        data: route_obj.get("data").map(|data| expr_to_json(source.resolve_local(data))),
+        title: route_obj.get("title").map(handler_name),
+        redirect_to: route_obj.get("redirectTo").map(handler_name),
+        path_match: route_obj.get_str("pathMatch").map(str::to_string),
+        outlet: route_obj.get_str("outlet").map(str::to_string),
+        providers: extract_array_property(source, route_obj, "providers"),
+        project_info: ProjectInfo::default(),
+    })
+}
+
+// Guards listed under a route key. Class guards wrapped in
+// `mapToCanActivate([AuthGuard])` and friends are unwrapped.
+fn route_handlers(source: &SourceFile, route_obj: &ObjectLit, key: &str) -> Vec<String> {
+    let Some(value) = route_obj.get(key) else {
+        return Vec::new();
+    };
+
+    let mut handlers = Vec::new();
+    for item in source.resolve_local(value).as_array().unwrap_or(std::slice::from_ref(value)) {
+        match item.callee_path() {
+            Some(callee) if callee.starts_with("mapTo") => {
+                let mapped = item.call_args().first().and_then(Expr::as_array).unwrap_or_default();
+                handlers.extend(mapped.iter().map(handler_name));
+            }
+            _ => handlers.push(handler_name(item)),
+        }
+    }
+    handlers
+}
+
+// How a guard, resolver, title or redirect is shown: its name, a factory call
+// such as `roleGuard(...)`, the string itself, or `(inline)` for functions
+fn handler_name(expr: &Expr) -> String {
+    match expr {
+        Expr::Str(s) | Expr::Template(s) => s.clone(),
+        Expr::Call { callee, .. } => format!("{}(...)", callee.path().unwrap_or_default()),
+        Expr::Arrow { .. } => "(inline)".to_string(),
+        Expr::Other(text) if text.starts_with("function") => "(inline)".to_string(),
+        _ => expr.path().unwrap_or_else(|| "(expression)".to_string()),
+    }
+}
+
+// Static route `data` as JSON; anything computed is kept as its name
+fn expr_to_json(expr: &Expr) -> serde_json::Value {
+    use serde_json::Value;
+
+    match expr {
+        Expr::Str(s) | Expr::Template(s) => Value::String(s.clone()),
+        Expr::Number(n) => n.parse::<f64>().map(Value::from).unwrap_or_else(|_| Value::String(n.clone())),
+        Expr::Ident(name) if name == "true" || name == "false" => Value::Bool(name == "true"),
+        Expr::Ident(name) if name == "null" => Value::Null,
+        Expr::Array(items) => Value::Array(items.iter().map(expr_to_json).collect()),
+        Expr::Object(obj) => Value::Object(obj.props.iter().map(|p| (p.key.clone(), expr_to_json(&p.value))).collect()),
+        _ => Value::String(handler_name(expr)),
+    }
+}
+
+fn set_route_project(routes: &mut [Route], project_info: &ProjectInfo) {
+    for route in routes {
+        route.project_info = project_info.clone();
+        set_route_project(&mut route.children, project_info);
+    }
+}
+
+fn join_route_path(parent: &str, path: &str) -> String {
+    let parent = parent.trim_end_matches('/');
+    let path = path.trim_matches('/');
+    match (parent.is_empty(), path.is_empty()) {
+        (true, true) => "/".to_string(),
+        (false, true) => parent.to_string(),
+        _ => format!("{}/{}", parent, path),
+    }
+}
+
+// Target of `loadChildren`/`loadComponent` in the `specifier#Export` form of
+// the old string syntax: `() => import('./admin.routes').then(m => m.ROUTES)`
+// becomes `./admin.routes#ROUTES`, and a bare `import()` of a default export
+// stays `./admin.routes`.
+fn lazy_import_target(expr: &Expr) -> Option<String> {
+    if let Some(target) = expr.as_str() {
+        return Some(target.to_string());
+    }
+
+    let mut specifier = None;
+    let mut export = None;
+    expr.walk(&mut |e| match e {
+        Expr::Call { callee, args, .. } if matches!(callee.as_ref(), Expr::Ident(name) if name == "import") => {
+            specifier = args.first().and_then(Expr::as_str);
+        }
+        Expr::Call { callee, args, .. } if matches!(callee.as_ref(), Expr::Member { property, .. } if property == "then") => {
+            if let Some(Expr::Arrow { params, body }) = args.first()
+                && let Expr::Member { object, property } = body.as_ref()
+                && matches!(object.as_ref(), Expr::Ident(name) if params.contains(name))
+            {
+                export = Some(property.as_str());
+            }
+        }
+        _ => {}
+    });
+
+    let specifier = specifier?;
+    Some(match export {
+        Some(export) if export != "default" => format!("{}#{}", specifier, export),
+        _ => specifier.to_string(),
     })
 }
 
+// Whether a file might configure the router without following the usual
+// file naming, checked on the raw text before parsing
+fn mentions_router(content: &str) -> bool {
+    content.contains("provideRouter") || content.contains("RouterModule.for")
+}
+
+fn is_routes_type(type_annotation: &str) -> bool {
+    matches!(type_annotation, "Routes" | "Route[]" | "Array<Route>")
+}
+
+// First arguments of every router call in a file, wherever they appear
+fn router_call_arguments<'a>(source: &'a SourceFile) -> Vec<&'a Expr> {
+    let mut arguments = Vec::new();
+    let mut visit = |expr: &'a Expr| {
+        if let Some(callee) = expr.callee_path()
+            && matches!(callee.as_str(), "provideRouter" | "RouterModule.forRoot" | "RouterModule.forChild")
+            && let Some(first) = expr.call_args().first()
+        {
+            arguments.push(first);
+        }
+    };
+
+    for class in &source.classes {
+        for decorator in &class.decorators {
+            for arg in &decorator.args {
+                arg.walk(&mut visit);
+            }
+        }
+    }
+    for variable in &source.variables {
+        if let Some(ref initializer) = variable.initializer {
+            initializer.walk(&mut visit);
+        }
+    }
+    for function in &source.functions {
+        function.body.walk(&mut visit);
+    }
+    for expr in &source.expressions {
+        expr.walk(&mut visit);
+    }
+
+    arguments
+}
+
 fn find_related_file(dir: &Path, base_name: &str, extension: &str) -> Option<PathBuf> {
     let target_file = format!("{}.{}", base_name, extension);
     let path = dir.join(&target_file);
@@ -738,3 +1930,118 @@ fn pascal_case(s: &str) -> String {
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excludes_survive_a_later_config() {
+        let dir = tempfile::tempdir().unwrap();
+        for sub in ["src", "gen"] {
+            fs::create_dir_all(dir.path().join(sub)).unwrap();
+            let class = format!("@Component({{ selector: 'app-{0}' }})\nexport class {0}Component {{}}", sub);
+            fs::write(dir.path().join(sub).join(format!("{}.component.ts", sub)), class).unwrap();
+        }
+
+        let mut analyzer = Analyzer::new(dir.path().to_path_buf(), AnalyzerOptions::default());
+        analyzer.exclude(&["gen/**".to_string()]).unwrap();
+        analyzer.apply_config(Config::default()).unwrap();
+
+        let names: Vec<String> = analyzer.analyze_components().unwrap().into_iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["srcComponent"]);
+    }
+
+    #[test]
+    fn tokens_are_traced_through_aliases_and_barrels() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/app/core")).unwrap();
+        fs::write(root.join("tsconfig.json"), r#"{ "compilerOptions": { "paths": { "@core": ["src/app/core/index.ts"] } } }"#).unwrap();
+        fs::write(
+            root.join("src/app/core/tokens.ts"),
+            "export const API_URL = new InjectionToken<string>('api url');",
+        ).unwrap();
+        fs::write(root.join("src/app/core/index.ts"), "export * from './tokens';").unwrap();
+        fs::write(
+            root.join("src/app/api.service.ts"),
+            "import { API_URL } from '@core';\n\
+             import { API_URL as BASE } from './core';\n\
+             @Injectable()\n\
+             export class ApiService {\n\
+                 url = inject(API_URL);\n\
+                 constructor(@Inject(BASE) base: string) {}\n\
+             }",
+        ).unwrap();
+
+        let mut analyzer = Analyzer::new(root.to_path_buf(), AnalyzerOptions::default());
+        let graph = analyzer.analyze_injection().unwrap();
+
+        let declaring = TokenSource::Project(PathBuf::from("src/app/core/tokens.ts"));
+        assert_eq!(graph.edges.len(), 2);
+        assert!(graph.edges.iter().all(|edge| edge.provider == declaring));
+        assert_eq!(graph.injection_tokens[0].consumers, vec!["ApiService"]);
+    }
+
+    #[test]
+    fn only_files_mentioning_the_router_are_parsed_for_routes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("main.ts"), "bootstrapApplication(App, { providers: [provideRouter([{ path: 'home', component: Home }])] });").unwrap();
+        fs::write(root.join("util.ts"), "export const answer = 42;").unwrap();
+
+        let mut analyzer = Analyzer::new(root.to_path_buf(), AnalyzerOptions::default());
+        let routes = analyzer.analyze_routes().unwrap();
+
+        assert_eq!(routes.iter().map(|r| r.full_path.as_str()).collect::<Vec<_>>(), vec!["/home"]);
+        let sources = analyzer.sources.lock().unwrap();
+        assert!(sources.contains_key(&root.join("main.ts")));
+        assert!(!sources.contains_key(&root.join("util.ts")));
+    }
+
+    #[test]
+    fn lazy_routes_are_spliced_under_their_parents() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let files = [
+            ("src/app/app.routes.ts", "export const routes: Routes = [\n\
+                { path: 'admin', loadChildren: () => import('./admin/admin.module').then(m => m.AdminModule) },\n\
+                { path: 'loop', loadChildren: () => import('./loop.routes').then(m => m.LOOP_ROUTES) },\n\
+            ];"),
+            ("src/app/admin/admin.module.ts", "import { AdminRoutingModule } from './admin-routing.module';\n\
+                @NgModule({ imports: [AdminRoutingModule] })\n\
+                export class AdminModule {}"),
+            ("src/app/admin/admin-routing.module.ts", "const routes: Routes = [\n\
+                { path: 'users', loadChildren: () => import('./users/users.routes').then(m => m.USER_ROUTES) },\n\
+            ];\n\
+            @NgModule({ imports: [RouterModule.forChild(routes)] })\n\
+            export class AdminRoutingModule {}"),
+            ("src/app/admin/users/users.routes.ts", "export const OTHER_ROUTES: Routes = [{ path: 'other', component: OtherComponent }];\n\
+                export const USER_ROUTES: Routes = [{ path: ':id', component: UserComponent }];"),
+            ("src/app/loop.routes.ts", "export const LOOP_ROUTES: Routes = [\n\
+                { path: 'again', loadChildren: () => import('./loop.routes').then(m => m.LOOP_ROUTES) },\n\
+            ];"),
+        ];
+        for (file, content) in files {
+            fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+            fs::write(root.join(file), content).unwrap();
+        }
+
+        let mut analyzer = Analyzer::new(root.to_path_buf(), AnalyzerOptions::default());
+        let routes = analyzer.analyze_routes().unwrap();
+
+        fn full_paths(routes: &[Route], out: &mut Vec<String>) {
+            for route in routes {
+                out.push(route.full_path.clone());
+                full_paths(&route.children, out);
+            }
+        }
+        let mut paths = Vec::new();
+        full_paths(&routes, &mut paths);
+
+        // Loaded files are not roots of their own, the NgModule leads to its
+        // routing module, `#USER_ROUTES` picks one array and the loop ends
+        assert_eq!(paths, vec!["/admin", "/admin/users", "/admin/users/:id", "/loop", "/loop/again"]);
+        assert_eq!(routes[0].lazy_file, Some(PathBuf::from("src/app/admin/admin.module.ts")));
+    }
+}
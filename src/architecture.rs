@@ -0,0 +1,256 @@
+// src/architecture.rs
+//
+// Layer rules for the dependency graph. Layers are sets of files picked by
+// path globs; rules say which layers a layer may (or may not) import from.
+
+use anyhow::{ Context, Result };
+use globset::{ Glob, GlobSet, GlobSetBuilder };
+use serde::Deserialize;
+use std::path::{ Component, Path };
+
+use crate::models::{ ArchitectureViolation, Dependency, DependencyTarget };
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LayerConfig {
+    pub name: String,
+    // Globs relative to the config file, e.g. `src/app/core/**`
+    pub paths: Vec<String>,
+    // Each directory matched by the first `*` of a path (one per feature in
+    // `src/app/features/*/**`) may not import from its siblings
+    #[serde(default)]
+    pub isolated: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RuleConfig {
+    pub name: String,
+    pub from: String,
+    // Layers `from` may import; any other layer is a violation
+    pub allow: Option<Vec<String>>,
+    // Layers `from` must not import
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+struct Layer<'a> {
+    config: &'a LayerConfig,
+    globs: GlobSet,
+}
+
+impl Layer<'_> {
+    // For isolated layers, which slice of the layer a file is in
+    fn slice<'p>(&self, path: &'p Path) -> Option<Component<'p>> {
+        self.config.paths.iter().find_map(|pattern| {
+            let prefix: Vec<&str> = pattern.split('/').take_while(|segment| !segment.contains('*')).collect();
+            let depth = prefix.len();
+            let components: Vec<Component> = path.components().collect();
+
+            let matches_prefix = components.len() > depth
+                && prefix.iter().zip(&components).all(|(p, c)| c.as_os_str() == *p);
+            matches_prefix.then(|| components[depth])
+        })
+    }
+}
+
+// `prefix` is the analyzed root relative to the config file, which layer
+// paths are relative to
+pub fn check_architecture(
+    dependencies: &[Dependency],
+    layer_configs: &[LayerConfig],
+    rules: &[RuleConfig],
+    prefix: &Path
+) -> Result<Vec<ArchitectureViolation>> {
+    let mut layers = Vec::new();
+    for layer in layer_configs {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &layer.paths {
+            builder.add(Glob::new(pattern).context(format!("Invalid glob '{}' in layer '{}'", pattern, layer.name))?);
+        }
+        layers.push(Layer {
+            config: layer,
+            globs: builder.build()?,
+        });
+    }
+
+    for rule in rules {
+        for layer_name in std::iter::once(&rule.from).chain(rule.allow.iter().flatten()).chain(&rule.deny) {
+            if !layer_configs.iter().any(|l| &l.name == layer_name) {
+                anyhow::bail!("Rule '{}' refers to unknown layer '{}'", rule.name, layer_name);
+            }
+        }
+    }
+
+    // Files belong to the first layer that matches them
+    let layer_of = |path: &Path| layers.iter().find(|layer| layer.globs.is_match(path));
+
+    let mut violations = Vec::new();
+    for dep in dependencies {
+        let DependencyTarget::File(ref target) = dep.target else { continue };
+        let source = prefix.join(&dep.source);
+        let target_path = prefix.join(target);
+        let (Some(source_layer), Some(target_layer)) = (layer_of(&source), layer_of(&target_path)) else {
+            continue;
+        };
+
+        let mut broken_rules = Vec::new();
+
+        if source_layer.config.name == target_layer.config.name {
+            if source_layer.config.isolated && source_layer.slice(&source) != source_layer.slice(&target_path) {
+                broken_rules.push(format!("{} (isolated)", source_layer.config.name));
+            }
+        } else {
+            for rule in rules.iter().filter(|r| r.from == source_layer.config.name) {
+                let allowed = rule.allow.as_ref().is_none_or(|allow| allow.contains(&target_layer.config.name));
+                if !allowed || rule.deny.contains(&target_layer.config.name) {
+                    broken_rules.push(rule.name.clone());
+                }
+            }
+        }
+
+        for rule in broken_rules {
+            violations.push(ArchitectureViolation {
+                rule,
+                source: dep.source.clone(),
+                line: dep.line,
+                target: target.clone(),
+                source_layer: source_layer.config.name.clone(),
+                target_layer: target_layer.config.name.clone(),
+            });
+        }
+    }
+
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ DependencyKind, ImportType };
+    use std::path::PathBuf;
+
+    fn layer(name: &str, paths: &[&str], isolated: bool) -> LayerConfig {
+        LayerConfig {
+            name: name.to_string(),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+            isolated,
+        }
+    }
+
+    fn rule(name: &str, from: &str, allow: Option<&[&str]>, deny: &[&str]) -> RuleConfig {
+        let names = |layers: &[&str]| layers.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+        RuleConfig {
+            name: name.to_string(),
+            from: from.to_string(),
+            allow: allow.map(names),
+            deny: names(deny),
+        }
+    }
+
+    fn import(source: &str, target: &str) -> Dependency {
+        Dependency {
+            source: PathBuf::from(source),
+            specifier: "./x".to_string(),
+            target: DependencyTarget::File(PathBuf::from(target)),
+            import_type: ImportType::Module,
+            kind: DependencyKind::Import,
+            names: Vec::new(),
+            type_only: false,
+            dynamic: false,
+            line: 1,
+        }
+    }
+
+    fn layers() -> Vec<LayerConfig> {
+        vec![
+            layer("core", &["src/app/core/**"], false),
+            layer("shared", &["src/app/shared/**"], false),
+            layer("features", &["src/app/features/*/**"], true),
+            // Never reached for core files, which match `core` first
+            layer("everything", &["src/app/**"], false),
+        ]
+    }
+
+    fn broken(dependencies: &[Dependency], rules: &[RuleConfig]) -> Vec<(String, String, String)> {
+        check_architecture(dependencies, &layers(), rules, Path::new(""))
+            .unwrap()
+            .into_iter()
+            .map(|v| (v.rule, v.source_layer, v.target_layer))
+            .collect()
+    }
+
+    fn violation(rule: &str, source: &str, target: &str) -> (String, String, String) {
+        (rule.to_string(), source.to_string(), target.to_string())
+    }
+
+    #[test]
+    fn files_belong_to_the_first_matching_layer() {
+        let rules = [rule("core-is-independent", "core", Some(&[]), &[])];
+
+        assert_eq!(
+            broken(&[import("src/app/core/a.ts", "src/app/shared/b.ts")], &rules),
+            vec![violation("core-is-independent", "core", "shared")]
+        );
+        assert_eq!(
+            broken(&[import("src/app/core/a.ts", "src/app/main.ts")], &rules),
+            vec![violation("core-is-independent", "core", "everything")]
+        );
+        // Files outside every layer are not checked
+        assert!(broken(&[import("src/app/core/a.ts", "src/environments/env.ts")], &rules).is_empty());
+    }
+
+    #[test]
+    fn allow_and_deny_lists_are_applied() {
+        let rules = [
+            rule("shared-not-features", "shared", None, &["features"]),
+            rule("features-use-shared", "features", Some(&["shared", "core"]), &[]),
+        ];
+        let dependencies = [
+            import("src/app/shared/a.ts", "src/app/features/cart/b.ts"),
+            import("src/app/shared/a.ts", "src/app/core/b.ts"),
+            import("src/app/features/cart/a.ts", "src/app/shared/b.ts"),
+            import("src/app/features/cart/a.ts", "src/app/main.ts"),
+        ];
+
+        assert_eq!(broken(&dependencies, &rules), vec![
+            violation("shared-not-features", "shared", "features"),
+            violation("features-use-shared", "features", "everything"),
+        ]);
+    }
+
+    #[test]
+    fn isolated_layers_forbid_imports_between_slices() {
+        let dependencies = [
+            import("src/app/features/cart/a.ts", "src/app/features/cart/items/b.ts"),
+            import("src/app/features/cart/a.ts", "src/app/features/checkout/b.ts"),
+            import("src/app/shared/a.ts", "src/app/shared/deep/b.ts"),
+        ];
+
+        assert_eq!(broken(&dependencies, &[]), vec![violation("features (isolated)", "features", "features")]);
+    }
+
+    #[test]
+    fn paths_are_relative_to_the_config_file() {
+        let dependencies = [import("src/app/features/cart/a.ts", "src/app/features/checkout/b.ts")];
+        let layers = [layer("features", &["web/src/app/features/*/**"], true)];
+
+        let violations = check_architecture(&dependencies, &layers, &[], Path::new("web")).unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].source, PathBuf::from("src/app/features/cart/a.ts"));
+    }
+
+    #[test]
+    fn rules_must_name_known_layers() {
+        let rules = [rule("typo", "core", None, &["sharde"])];
+
+        let err = check_architecture(&[], &layers(), &rules, Path::new("")).unwrap_err();
+        assert_eq!(err.to_string(), "Rule 'typo' refers to unknown layer 'sharde'");
+    }
+
+    #[test]
+    fn invalid_globs_are_reported() {
+        let layers = [layer("broken", &["src/[app"], false)];
+
+        let err = check_architecture(&[], &layers, &[], Path::new("")).unwrap_err();
+        assert!(err.to_string().contains("Invalid glob 'src/[app' in layer 'broken'"));
+    }
+}
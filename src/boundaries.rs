@@ -0,0 +1,256 @@
+// src/boundaries.rs
+//
+// Offline check of Nx module boundaries: the `depConstraints` of
+// `@nx/enforce-module-boundaries` applied to the resolved file graph.
+
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::models::{ BoundaryViolation, Dependency, DependencyTarget };
+use crate::utils::json_utils;
+use crate::workspace::{ ProjectType, Workspace };
+
+const ESLINT_CONFIGS: &[&str] = &[".eslintrc.json", ".eslintrc"];
+const RULE_NAMES: &[&str] = &["@nx/enforce-module-boundaries", "@nrwl/nx/enforce-module-boundaries"];
+
+#[derive(Debug)]
+pub struct DepConstraint {
+    pub source_tag: String,
+    pub only_depend_on_libs_with_tags: Vec<String>,
+    pub not_depend_on_libs_with_tags: Vec<String>,
+}
+
+// Reads `depConstraints` from the root ESLint config, in its top-level rules
+// or any of its overrides. Flat `eslint.config.js` files are code and are
+// not read.
+pub fn load_constraints(root: &Path) -> Vec<DepConstraint> {
+    let Some(config) = ESLINT_CONFIGS
+        .iter()
+        .filter_map(|name| fs::read_to_string(root.join(name)).ok())
+        .find_map(|content| json_utils::parse_jsonc(&content).ok())
+    else {
+        return Vec::new();
+    };
+
+    let rule_sets = std::iter::once(&config["rules"])
+        .chain(config["overrides"].as_array().into_iter().flatten().map(|o| &o["rules"]));
+
+    let mut constraints = Vec::new();
+    for rules in rule_sets {
+        for rule_name in RULE_NAMES {
+            // `["error", { depConstraints: [...] }]`
+            let Some(constraint_list) = rules[*rule_name][1]["depConstraints"].as_array() else { continue };
+            constraints.extend(constraint_list.iter().map(|c| DepConstraint {
+                source_tag: c["sourceTag"].as_str().unwrap_or("*").to_string(),
+                only_depend_on_libs_with_tags: string_list(&c["onlyDependOnLibsWithTags"]),
+                not_depend_on_libs_with_tags: string_list(&c["notDependOnLibsWithTags"]),
+            }));
+        }
+    }
+
+    constraints
+}
+
+pub fn check_boundaries(
+    dependencies: &[Dependency],
+    workspace: &Workspace,
+    constraints: &[DepConstraint]
+) -> Vec<BoundaryViolation> {
+    let mut violations = Vec::new();
+
+    for dep in dependencies {
+        let DependencyTarget::File(ref target) = dep.target else { continue };
+        let (Some(source_project), Some(target_project)) =
+            (workspace.project_for(&dep.source), workspace.project_for(target))
+        else {
+            continue;
+        };
+        if source_project.name == target_project.name {
+            continue;
+        }
+
+        let mut reasons = Vec::new();
+
+        if target_project.project_type == ProjectType::Application {
+            reasons.push(format!("imports the application '{}'", target_project.name));
+        }
+
+        for constraint in constraints {
+            if !source_project.tags.iter().any(|tag| tag_matches(&constraint.source_tag, tag)) {
+                continue;
+            }
+
+            let allowed = &constraint.only_depend_on_libs_with_tags;
+            if !allowed.is_empty()
+                && !target_project.tags.iter().any(|tag| allowed.iter().any(|a| tag_matches(a, tag)))
+            {
+                reasons.push(format!(
+                    "'{}' may only depend on projects tagged {}",
+                    constraint.source_tag,
+                    allowed.join(", ")
+                ));
+            }
+
+            if let Some(banned) = target_project.tags.iter().find(|tag| {
+                constraint.not_depend_on_libs_with_tags.iter().any(|b| tag_matches(b, tag))
+            }) {
+                reasons.push(format!("'{}' must not depend on projects tagged {}", constraint.source_tag, banned));
+            }
+        }
+
+        for reason in reasons {
+            violations.push(BoundaryViolation {
+                source: dep.source.clone(),
+                line: dep.line,
+                specifier: dep.specifier.clone(),
+                target: target.clone(),
+                source_project: source_project.name.clone(),
+                target_project: target_project.name.clone(),
+                reason,
+            });
+        }
+    }
+
+    violations
+}
+
+// Nx accepts `*` for any tag and `*` wildcards inside a tag such as
+// `scope:*`. Regular expression tags (`/^scope.*/`) are not supported.
+fn tag_matches(pattern: &str, tag: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = tag.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.ends_with(last)
+}
+
+fn string_list(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|items| items.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ DependencyKind, ImportType };
+    use crate::workspace::WorkspaceProject;
+    use std::path::PathBuf;
+
+    fn project(name: &str, root: &str, project_type: ProjectType, tags: &[&str]) -> WorkspaceProject {
+        WorkspaceProject {
+            name: name.to_string(),
+            root: PathBuf::from(root),
+            source_root: None,
+            prefix: None,
+            project_type,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    fn import(source: &str, target: &str) -> Dependency {
+        Dependency {
+            source: PathBuf::from(source),
+            specifier: "@org/lib".to_string(),
+            target: DependencyTarget::File(PathBuf::from(target)),
+            import_type: ImportType::Module,
+            kind: DependencyKind::Import,
+            names: Vec::new(),
+            type_only: false,
+            dynamic: false,
+            line: 1,
+        }
+    }
+
+    fn constraint(source_tag: &str, only: &[&str], not: &[&str]) -> DepConstraint {
+        DepConstraint {
+            source_tag: source_tag.to_string(),
+            only_depend_on_libs_with_tags: only.iter().map(|t| t.to_string()).collect(),
+            not_depend_on_libs_with_tags: not.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    fn workspace() -> Workspace {
+        Workspace {
+            projects: vec![
+                project("shop", "apps/shop", ProjectType::Application, &["type:app", "scope:shop"]),
+                project("ui", "libs/ui", ProjectType::Library, &["type:ui", "scope:shared"]),
+                project("data", "libs/data", ProjectType::Library, &["type:data-access", "scope:admin"]),
+            ],
+        }
+    }
+
+    fn reasons(dependencies: &[Dependency], constraints: &[DepConstraint]) -> Vec<String> {
+        check_boundaries(dependencies, &workspace(), constraints).into_iter().map(|v| v.reason).collect()
+    }
+
+    #[test]
+    fn tags_match_exactly_or_by_wildcard() {
+        assert!(tag_matches("*", "anything"));
+        assert!(tag_matches("scope:shared", "scope:shared"));
+        assert!(!tag_matches("scope:shared", "scope:shared-ui"));
+        assert!(tag_matches("scope:*", "scope:admin"));
+        assert!(!tag_matches("scope:*", "type:ui"));
+        assert!(tag_matches("*:ui", "type:ui"));
+        assert!(tag_matches("type:*-access", "type:data-access"));
+        assert!(!tag_matches("a*a", "a"));
+    }
+
+    #[test]
+    fn only_depend_on_libs_with_tags_is_enforced() {
+        let constraints = [constraint("type:ui", &["type:ui", "type:util"], &[])];
+
+        assert_eq!(
+            reasons(&[import("libs/ui/src/a.ts", "libs/data/src/b.ts")], &constraints),
+            vec!["'type:ui' may only depend on projects tagged type:ui, type:util"]
+        );
+        assert!(reasons(&[import("apps/shop/src/a.ts", "libs/data/src/b.ts")], &constraints).is_empty());
+    }
+
+    #[test]
+    fn not_depend_on_libs_with_tags_is_enforced() {
+        let constraints = [constraint("scope:*", &[], &["scope:admin"])];
+
+        assert_eq!(
+            reasons(&[import("apps/shop/src/a.ts", "libs/data/src/b.ts")], &constraints),
+            vec!["'scope:*' must not depend on projects tagged scope:admin"]
+        );
+        assert!(reasons(&[import("apps/shop/src/a.ts", "libs/ui/src/b.ts")], &constraints).is_empty());
+    }
+
+    #[test]
+    fn importing_an_application_is_always_a_violation() {
+        assert_eq!(
+            reasons(&[import("libs/ui/src/a.ts", "apps/shop/src/main.ts")], &[]),
+            vec!["imports the application 'shop'"]
+        );
+    }
+
+    #[test]
+    fn imports_inside_a_project_or_outside_the_workspace_are_skipped() {
+        let constraints = [constraint("*", &["nothing"], &[])];
+
+        assert!(reasons(&[import("libs/ui/src/a.ts", "libs/ui/src/b.ts")], &constraints).is_empty());
+        assert!(reasons(&[import("tools/script.ts", "libs/ui/src/b.ts")], &constraints).is_empty());
+    }
+}
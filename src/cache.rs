@@ -0,0 +1,161 @@
+// src/cache.rs
+//
+// Parsed TypeScript sources kept on disk between runs. Entries are keyed by
+// the BLAKE3 hash of the file content and stored under a directory named for
+// the tool and AST versions, so an edited file, a new release or a change to
+// the parser output is always reparsed.
+
+use anyhow::{ Context, Result };
+use std::fs;
+use std::io::Write;
+use std::path::{ Path, PathBuf };
+
+use crate::typescript::{ self, SourceFile };
+
+// Relative to the analyzed root; `.angular` is never analyzed itself
+pub const DEFAULT_DIR: &str = ".angular/ngstructure-cache";
+
+// Bump whenever the AST types or what the parser produces for a file change,
+// so entries written by an older parser are never read back
+const AST_VERSION: u32 = 2;
+
+// Written in every cache directory; `clear` only touches directories that
+// have it, and there only the entries below `ENTRY_PREFIX`
+const MARKER_FILE: &str = "CACHEDIR.TAG";
+const MARKER_CONTENT: &str = "Signature: 8a477f597d28d172789f06886806bc55\n\
+# This directory is a parse cache created by angular-structure-analyzer.\n";
+const ENTRY_PREFIX: &str = "parse-";
+
+#[derive(Debug, Clone)]
+pub struct ParseCache {
+    dir: PathBuf,
+}
+
+impl ParseCache {
+    // Marks `dir` as a cache directory, creating it when needed. A directory
+    // that cannot be written leaves the cache without effect.
+    pub fn new(dir: &Path) -> ParseCache {
+        let marker = dir.join(MARKER_FILE);
+        if !marker.exists() {
+            let _ = fs::create_dir_all(dir).and_then(|_| fs::write(&marker, MARKER_CONTENT));
+        }
+
+        ParseCache {
+            dir: dir.join(format!("{}{}-ast{}", ENTRY_PREFIX, env!("CARGO_PKG_VERSION"), AST_VERSION)),
+        }
+    }
+
+    // Removes the entries of every tool version. Other files in `dir` are
+    // kept, and a directory without the marker is refused outright.
+    pub fn clear(dir: &Path) -> Result<()> {
+        if !dir.exists() {
+            return Ok(());
+        }
+
+        let marker = dir.join(MARKER_FILE);
+        if !marker.is_file() {
+            anyhow::bail!(
+                "'{}' is not a cache directory of this tool ({} is missing); not clearing it",
+                dir.display(),
+                MARKER_FILE
+            );
+        }
+
+        let entries = fs::read_dir(dir).context(format!("Failed to read '{}'", dir.display()))?;
+        for entry in entries {
+            let entry = entry?;
+            let is_cache_entry = entry.file_name().to_str().is_some_and(|name| name.starts_with(ENTRY_PREFIX));
+            if is_cache_entry && entry.file_type()?.is_dir() {
+                fs::remove_dir_all(entry.path())
+                    .context(format!("Failed to clear the cache in '{}'", entry.path().display()))?;
+            }
+        }
+
+        fs::remove_file(&marker).context(format!("Failed to remove '{}'", marker.display()))?;
+        // Only succeeds when nothing else was kept in the directory
+        let _ = fs::remove_dir(dir);
+        Ok(())
+    }
+
+    // The cached parse of `content`, or a fresh one that is stored for the
+    // next run. A cache that cannot be read or written only costs the parse.
+    pub(crate) fn parse(&self, content: &str) -> SourceFile {
+        let entry = self.entry_path(content);
+
+        if let Ok(bytes) = fs::read(&entry)
+            && let Ok(source) = bincode::deserialize(&bytes)
+        {
+            return source;
+        }
+
+        let source = typescript::parse(content);
+        let _ = self.store(&entry, &source);
+        source
+    }
+
+    fn entry_path(&self, content: &str) -> PathBuf {
+        let hash = blake3::hash(content.as_bytes()).to_hex();
+        self.dir.join(&hash[..2]).join(&hash[2..])
+    }
+
+    // Written to a temporary file first, so other runs never read half an entry
+    fn store(&self, entry: &Path, source: &SourceFile) -> Result<()> {
+        let dir = entry.parent().unwrap_or(&self.dir);
+        fs::create_dir_all(dir)?;
+
+        let mut file = tempfile::NamedTempFile::new_in(dir)?;
+        file.write_all(&bincode::serialize(source)?)?;
+        file.persist(entry)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clear_refuses_directories_without_the_marker() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("important.txt"), "keep").unwrap();
+
+        assert!(ParseCache::clear(dir.path()).is_err());
+        assert!(dir.path().join("important.txt").exists());
+    }
+
+    #[test]
+    fn clear_removes_only_cache_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("important.txt"), "keep").unwrap();
+
+        let cache = ParseCache::new(dir.path());
+        cache.parse("export class A {}");
+        assert!(cache.dir.is_dir());
+
+        ParseCache::clear(dir.path()).unwrap();
+        assert!(!cache.dir.exists());
+        assert!(!dir.path().join(MARKER_FILE).exists());
+        assert!(dir.path().join("important.txt").exists());
+    }
+
+    #[test]
+    fn clear_removes_an_emptied_directory() {
+        let parent = tempfile::tempdir().unwrap();
+        let dir = parent.path().join("cache");
+
+        ParseCache::new(&dir).parse("const a = 1;");
+        ParseCache::clear(&dir).unwrap();
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn cached_parses_match_fresh_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ParseCache::new(dir.path());
+        let content = "@Component({ selector: 'app-a' })\nexport class A {}";
+
+        let fresh = cache.parse(content);
+        let cached = cache.parse(content);
+        assert_eq!(format!("{:?}", fresh), format!("{:?}", cached));
+    }
+}
@@ -0,0 +1,162 @@
+// src/config.rs
+//
+// The `.ngstructure.toml` project configuration, found by walking up from the
+// analyzed path. Every setting is a default that command line flags override.
+
+use anyhow::{ Context, Result };
+use globset::{ Glob, GlobSet, GlobSetBuilder };
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use crate::models::FileType;
+
+pub use crate::architecture::{ LayerConfig, RuleConfig };
+
+pub const CONFIG_FILE: &str = ".ngstructure.toml";
+// Read like a .gitignore, in any directory of the project
+pub const IGNORE_FILE: &str = ".ngstructureignore";
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    // Globs relative to the directory of the config file. When `include` is
+    // set only files matching one of its globs are analyzed.
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub include_tests: Option<bool>,
+    pub include_styles: Option<bool>,
+    pub include_node_modules: Option<bool>,
+    pub max_depth: Option<usize>,
+    pub project: Option<String>,
+    // Relative to the config file; `.angular/ngstructure-cache` in the
+    // analyzed root by default
+    pub cache_dir: Option<PathBuf>,
+    // File name suffixes and the file type they stand for, such as
+    // `".store.ts" = "service"`
+    pub file_types: BTreeMap<String, FileType>,
+    pub output: OutputConfig,
+    // Subcommand flag defaults, keyed by subcommand name
+    pub commands: BTreeMap<String, CommandDefaults>,
+    pub layers: Vec<LayerConfig>,
+    pub rules: Vec<RuleConfig>,
+    // Directory the config file was read from
+    #[serde(skip)]
+    pub dir: PathBuf,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputConfig {
+    pub json: Option<bool>,
+    pub color: Option<ColorChoice>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    // Colored when stdout is a terminal
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommandDefaults {
+    pub detailed: Option<bool>,
+    pub graph: Option<bool>,
+    pub audit: Option<bool>,
+}
+
+impl Config {
+    // The nearest config file in the analyzed directory or one of its parents
+    pub fn discover(start: &Path) -> Result<Option<Config>> {
+        let start = start.canonicalize().context(format!("Failed to resolve '{}'", start.display()))?;
+
+        for dir in start.ancestors() {
+            let path = dir.join(CONFIG_FILE);
+            if path.is_file() {
+                return Config::load(&path).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn load(path: &Path) -> Result<Config> {
+        let content = fs::read_to_string(path).context(format!("Failed to read '{}'", path.display()))?;
+        let mut config: Config = toml::from_str(&content).context(format!("Failed to parse '{}'", path.display()))?;
+
+        let path = path.canonicalize().context(format!("Failed to resolve '{}'", path.display()))?;
+        config.dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Ok(config)
+    }
+
+    pub fn command(&self, name: &str) -> Option<&CommandDefaults> {
+        self.commands.get(name)
+    }
+
+    // Where the analyzed root lies relative to the config file, which all
+    // configured globs are relative to
+    pub fn root_prefix(&self, root: &Path) -> PathBuf {
+        if self.dir.as_os_str().is_empty() {
+            return PathBuf::new();
+        }
+
+        root.canonicalize()
+            .ok()
+            .and_then(|root| root.strip_prefix(&self.dir).ok().map(Path::to_path_buf))
+            .unwrap_or_default()
+    }
+}
+
+// The include and exclude globs of a config, applied to paths relative to the
+// analyzed root
+#[derive(Debug, Default, Clone)]
+pub struct FileFilter {
+    prefix: PathBuf,
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+    // `--exclude` globs, which are relative to the analyzed root
+    root_exclude: Option<GlobSet>,
+}
+
+impl FileFilter {
+    pub fn new(config: &Config, root: &Path) -> Result<FileFilter> {
+        Ok(FileFilter {
+            prefix: config.root_prefix(root),
+            include: build_glob_set(&config.include, "include")?,
+            exclude: build_glob_set(&config.exclude, "exclude")?,
+            root_exclude: None,
+        })
+    }
+
+    pub fn set_root_excludes(&mut self, patterns: &[String]) -> Result<()> {
+        self.root_exclude = build_glob_set(patterns, "--exclude")?;
+        Ok(())
+    }
+
+    pub fn is_excluded(&self, relative_path: &Path) -> bool {
+        self.exclude.as_ref().is_some_and(|globs| globs.is_match(self.prefix.join(relative_path)))
+            || self.root_exclude.as_ref().is_some_and(|globs| globs.is_match(relative_path))
+    }
+
+    pub fn includes_file(&self, relative_path: &Path) -> bool {
+        self.include.as_ref().is_none_or(|globs| globs.is_match(self.prefix.join(relative_path)))
+            && !self.is_excluded(relative_path)
+    }
+}
+
+fn build_glob_set(patterns: &[String], key: &str) -> Result<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).context(format!("Invalid glob '{}' in {}", pattern, key))?);
+    }
+    Ok(Some(builder.build()?))
+}
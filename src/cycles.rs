@@ -0,0 +1,224 @@
+// src/cycles.rs
+//
+// Finds import cycles in the resolved file graph: Tarjan's strongly connected
+// components, then one ordered chain through each component.
+
+use std::collections::hash_map::Entry;
+use std::collections::{ BTreeMap, BTreeSet, HashMap, VecDeque };
+use std::path::Path;
+
+use crate::models::{ Cycle, Dependency, DependencyTarget };
+
+pub fn find_cycles(dependencies: &[Dependency]) -> Vec<Cycle> {
+    // Sorted so the output does not depend on file system order
+    let mut graph: BTreeMap<&Path, BTreeSet<&Path>> = BTreeMap::new();
+    for dep in dependencies {
+        // Type-only imports are erased and `import()` runs after the module
+        // has loaded, so neither can leave a binding undefined
+        if dep.type_only || dep.dynamic {
+            continue;
+        }
+        if let DependencyTarget::File(ref target) = dep.target {
+            graph.entry(&dep.source).or_default().insert(target);
+            graph.entry(target).or_default();
+        }
+    }
+
+    let nodes: Vec<&Path> = graph.keys().copied().collect();
+    let index_of: HashMap<&Path, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+    let edges: Vec<Vec<usize>> = nodes
+        .iter()
+        .map(|node| graph[node].iter().map(|target| index_of[target]).collect())
+        .collect();
+
+    let mut cycles: Vec<Cycle> = strongly_connected_components(&edges)
+        .into_iter()
+        .filter(|component| component.len() > 1 || edges[component[0]].contains(&component[0]))
+        .map(|mut component| {
+            component.sort_unstable();
+            let chain = shortest_cycle(&edges, &component);
+            Cycle {
+                chain: chain.iter().map(|&i| nodes[i].to_path_buf()).collect(),
+                files: component.iter().map(|&i| nodes[i].to_path_buf()).collect(),
+            }
+        })
+        .collect();
+
+    cycles.sort_by(|a, b| a.files.cmp(&b.files));
+    cycles
+}
+
+// Iterative Tarjan, so deep import chains cannot overflow the stack
+fn strongly_connected_components(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+
+    let mut index = vec![UNVISITED; edges.len()];
+    let mut low_link = vec![0; edges.len()];
+    let mut on_stack = vec![false; edges.len()];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut next_index = 0;
+
+    for start in 0..edges.len() {
+        if index[start] != UNVISITED {
+            continue;
+        }
+
+        // (node, position of the next edge to visit)
+        let mut work = vec![(start, 0)];
+        index[start] = next_index;
+        low_link[start] = next_index;
+        next_index += 1;
+        stack.push(start);
+        on_stack[start] = true;
+
+        while let Some(&mut (node, ref mut edge)) = work.last_mut() {
+            if let Some(&next) = edges[node].get(*edge) {
+                *edge += 1;
+                if index[next] == UNVISITED {
+                    index[next] = next_index;
+                    low_link[next] = next_index;
+                    next_index += 1;
+                    stack.push(next);
+                    on_stack[next] = true;
+                    work.push((next, 0));
+                } else if on_stack[next] {
+                    low_link[node] = low_link[node].min(index[next]);
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some(&(parent, _)) = work.last() {
+                low_link[parent] = low_link[parent].min(low_link[node]);
+            }
+
+            if low_link[node] == index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+// The shortest chain that leaves the first file of a component and comes back
+// to it, staying inside the component
+fn shortest_cycle(edges: &[Vec<usize>], component: &[usize]) -> Vec<usize> {
+    let start = component[0];
+    let mut previous: HashMap<usize, usize> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        for &next in &edges[node] {
+            if component.binary_search(&next).is_err() {
+                continue;
+            }
+
+            if next == start {
+                let mut chain = vec![node];
+                let mut current = node;
+                while current != start {
+                    current = previous[&current];
+                    chain.push(current);
+                }
+                chain.reverse();
+                return chain;
+            }
+
+            if let Entry::Vacant(entry) = previous.entry(next) {
+                entry.insert(node);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    vec![start]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ DependencyKind, ImportType };
+    use std::path::PathBuf;
+
+    fn import(source: &str, target: &str) -> Dependency {
+        Dependency {
+            source: PathBuf::from(source),
+            specifier: format!("./{}", target),
+            target: DependencyTarget::File(PathBuf::from(target)),
+            import_type: ImportType::Module,
+            kind: DependencyKind::Import,
+            names: Vec::new(),
+            type_only: false,
+            dynamic: false,
+            line: 1,
+        }
+    }
+
+    fn paths(names: &[&str]) -> Vec<PathBuf> {
+        names.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn finds_a_two_file_cycle() {
+        let cycles = find_cycles(&[import("a.ts", "b.ts"), import("b.ts", "a.ts"), import("b.ts", "c.ts")]);
+
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].chain, paths(&["a.ts", "b.ts"]));
+        assert_eq!(cycles[0].files, paths(&["a.ts", "b.ts"]));
+    }
+
+    #[test]
+    fn finds_a_self_import() {
+        let cycles = find_cycles(&[import("a.ts", "a.ts"), import("a.ts", "b.ts")]);
+
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].chain, paths(&["a.ts"]));
+        assert_eq!(cycles[0].files, paths(&["a.ts"]));
+    }
+
+    #[test]
+    fn the_chain_is_the_shortest_way_back_through_the_component() {
+        let cycles = find_cycles(&[
+            import("a.ts", "b.ts"),
+            import("b.ts", "a.ts"),
+            import("b.ts", "c.ts"),
+            import("c.ts", "a.ts"),
+        ]);
+
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].chain, paths(&["a.ts", "b.ts"]));
+        assert_eq!(cycles[0].files, paths(&["a.ts", "b.ts", "c.ts"]));
+    }
+
+    #[test]
+    fn type_only_and_dynamic_imports_close_no_cycle() {
+        let mut type_only = import("b.ts", "a.ts");
+        type_only.type_only = true;
+        let mut dynamic = import("d.ts", "c.ts");
+        dynamic.dynamic = true;
+
+        let cycles = find_cycles(&[import("a.ts", "b.ts"), type_only, import("c.ts", "d.ts"), dynamic]);
+        assert!(cycles.is_empty());
+    }
+
+    #[test]
+    fn deep_chains_do_not_overflow_the_stack() {
+        let files: Vec<String> = (0..20_000).map(|i| format!("{}.ts", i)).collect();
+        let mut dependencies: Vec<Dependency> = files.windows(2).map(|pair| import(&pair[0], &pair[1])).collect();
+        dependencies.push(import(&files[files.len() - 1], &files[0]));
+
+        let cycles = find_cycles(&dependencies);
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].files.len(), files.len());
+    }
+}
@@ -1,49 +1,41 @@
 // src/formatter/graph.rs
 use anyhow::Result;
+use std::io::Write;
 use colored::*;
 use std::collections::{HashMap, HashSet};
 use crate::models::*;
 
-pub fn output_dependencies(dependencies: &[Dependency]) -> Result<()> {
-    println!("\n{} Dependency Graph:", "GRAPH:".green().bold());
+pub fn output_dependencies(out: &mut impl Write, dependencies: &[Dependency]) -> Result<()> {
+    writeln!(out, "\n{} Dependency Graph:", "GRAPH:".green().bold())?;
     
     if dependencies.is_empty() {
-        println!("  No dependencies found");
+        writeln!(out, "  No dependencies found")?;
         return Ok(());
     }
     
-    // Create a graph representation
+    // Create a graph representation keyed by project-relative file paths
     let mut graph = HashMap::new();
-    let mut nodes = HashSet::new();
     
     for dep in dependencies {
-        let source = dep.source.file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("unknown")
-            .to_string();
-            
-        let target = dep.target.clone();
-        
-        nodes.insert(source.clone());
-        nodes.insert(target.clone());
-        
+        let source = dep.source.display().to_string();
         let entry = graph.entry(source).or_insert_with(HashSet::new);
-        entry.insert((target, dep.import_type.clone()));
+        entry.insert((dep.target.clone(), dep.import_type.clone()));
     }
     
     // Print the graph
-    print_ascii_graph(&graph);
+    print_ascii_graph(out, &graph)?;
     
     Ok(())
 }
 
-fn print_ascii_graph(graph: &HashMap<String, HashSet<(String, ImportType)>>) {
+fn print_ascii_graph(out: &mut impl Write, graph: &HashMap<String, HashSet<(DependencyTarget, ImportType)>>) -> Result<()> {
     let mut sorted_sources: Vec<_> = graph.keys().collect();
     sorted_sources.sort();
     
     for source in sorted_sources {
-        println!("  {} {}:", "Node:".cyan(), source.yellow());
+        writeln!(out, "  {} {}:", "Node:".cyan(), source.yellow())?;
         
+        // Project files first, then packages and unresolved imports
         let deps = &graph[source];
         let mut sorted_deps: Vec<_> = deps.iter().collect();
         sorted_deps.sort_by(|a, b| a.0.cmp(&b.0));
@@ -61,9 +53,90 @@ fn print_ascii_graph(graph: &HashMap<String, HashSet<(String, ImportType)>>) {
                 ImportType::Other => "Other".normal(),
             };
             
-            println!("    └─→ {} ({})", target.green(), type_str);
+            let target_str = match target {
+                DependencyTarget::File(_) => target.to_string().green(),
+                DependencyTarget::Package(_) => target.to_string().blue(),
+                DependencyTarget::Unresolved(_) => target.to_string().red(),
+            };
+            
+            writeln!(out, "    └─→ {} ({})", target_str, type_str)?;
         }
         
-        println!();
+        writeln!(out)?;
     }
+
+    Ok(())
+}
+
+pub fn output_render_tree(out: &mut impl Write, tree: &RenderTree) -> Result<()> {
+    writeln!(out, "\n{} Render Graph:", "GRAPH:".green().bold())?;
+
+    if tree.edges.is_empty() {
+        writeln!(out, "  No template usages found")?;
+        return Ok(());
+    }
+
+    let mut graph: HashMap<&str, HashSet<(&str, RenderKind)>> = HashMap::new();
+    for edge in &tree.edges {
+        graph.entry(&edge.parent).or_default().insert((&edge.child, edge.kind));
+    }
+
+    let mut parents: Vec<_> = graph.keys().collect();
+    parents.sort();
+
+    for parent in parents {
+        writeln!(out, "  {} {}:", "Node:".cyan(), parent.yellow())?;
+
+        let mut children: Vec<_> = graph[parent].iter().collect();
+        children.sort();
+
+        for (child, kind) in children {
+            let kind_str = match kind {
+                RenderKind::Component => "Component".cyan(),
+                RenderKind::Directive => "Directive".magenta(),
+            };
+            writeln!(out, "    └─→ {} ({})", child.green(), kind_str)?;
+        }
+
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+pub fn output_injection(out: &mut impl Write, injection: &InjectionGraph) -> Result<()> {
+    writeln!(out, "\n{} Injection Graph:", "GRAPH:".green().bold())?;
+
+    if injection.edges.is_empty() {
+        writeln!(out, "  No injections found")?;
+        return Ok(());
+    }
+
+    let mut graph: HashMap<&str, HashSet<(&str, &TokenSource)>> = HashMap::new();
+    for edge in &injection.edges {
+        graph.entry(&edge.consumer).or_default().insert((&edge.token, &edge.provider));
+    }
+
+    let mut consumers: Vec<_> = graph.keys().collect();
+    consumers.sort();
+
+    for consumer in consumers {
+        writeln!(out, "  {} {}:", "Node:".cyan(), consumer.yellow())?;
+
+        let mut tokens: Vec<_> = graph[consumer].iter().collect();
+        tokens.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (token, provider) in tokens {
+            let provider_str = match provider {
+                TokenSource::Project(_) => "Project".green(),
+                TokenSource::Package(package) => package.as_str().blue(),
+                TokenSource::Unresolved => "Unresolved".red(),
+            };
+            writeln!(out, "    └─→ {} ({})", token.green(), provider_str)?;
+        }
+
+        writeln!(out)?;
+    }
+
+    Ok(())
 }
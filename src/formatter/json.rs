@@ -1,41 +1,84 @@
 // src/formatter/json.rs
 use anyhow::Result;
+use std::io::Write;
 use serde_json;
 
 use crate::models::*;
 
-pub fn output_structure(structure: &ProjectStructure) -> Result<()> {
+pub fn output_structure(out: &mut impl Write, structure: &ProjectStructure) -> Result<()> {
     let json = serde_json::to_string_pretty(structure)?;
-    println!("{}", json);
+    writeln!(out, "{}", json)?;
     Ok(())
 }
 
-pub fn output_components(components: &[Component]) -> Result<()> {
+pub fn output_components(out: &mut impl Write, components: &[Component]) -> Result<()> {
     let json = serde_json::to_string_pretty(components)?;
-    println!("{}", json);
+    writeln!(out, "{}", json)?;
     Ok(())
 }
 
-pub fn output_services(services: &[Service]) -> Result<()> {
+pub fn output_services(out: &mut impl Write, services: &[Service]) -> Result<()> {
     let json = serde_json::to_string_pretty(services)?;
-    println!("{}", json);
+    writeln!(out, "{}", json)?;
     Ok(())
 }
 
-pub fn output_modules(modules: &[Module]) -> Result<()> {
+pub fn output_modules(out: &mut impl Write, modules: &[Module]) -> Result<()> {
     let json = serde_json::to_string_pretty(modules)?;
-    println!("{}", json);
+    writeln!(out, "{}", json)?;
     Ok(())
 }
 
-pub fn output_dependencies(dependencies: &[Dependency]) -> Result<()> {
+pub fn output_dependencies(out: &mut impl Write, dependencies: &[Dependency]) -> Result<()> {
     let json = serde_json::to_string_pretty(dependencies)?;
-    println!("{}", json);
+    writeln!(out, "{}", json)?;
     Ok(())
 }
 
-pub fn output_routes(routes: &[Route]) -> Result<()> {
+pub fn output_cycles(out: &mut impl Write, cycles: &[Cycle]) -> Result<()> {
+    let json = serde_json::to_string_pretty(cycles)?;
+    writeln!(out, "{}", json)?;
+    Ok(())
+}
+
+pub fn output_boundary_violations(out: &mut impl Write, violations: &[BoundaryViolation]) -> Result<()> {
+    let json = serde_json::to_string_pretty(violations)?;
+    writeln!(out, "{}", json)?;
+    Ok(())
+}
+
+pub fn output_architecture_violations(out: &mut impl Write, violations: &[ArchitectureViolation]) -> Result<()> {
+    let json = serde_json::to_string_pretty(violations)?;
+    writeln!(out, "{}", json)?;
+    Ok(())
+}
+
+pub fn output_routes(out: &mut impl Write, routes: &[Route]) -> Result<()> {
     let json = serde_json::to_string_pretty(routes)?;
-    println!("{}", json);
+    writeln!(out, "{}", json)?;
+    Ok(())
+}
+
+pub fn output_route_findings(out: &mut impl Write, findings: &[RouteFinding]) -> Result<()> {
+    let json = serde_json::to_string_pretty(findings)?;
+    writeln!(out, "{}", json)?;
+    Ok(())
+}
+
+pub fn output_render_tree(out: &mut impl Write, tree: &RenderTree) -> Result<()> {
+    let json = serde_json::to_string_pretty(tree)?;
+    writeln!(out, "{}", json)?;
+    Ok(())
+}
+
+pub fn output_injection(out: &mut impl Write, injection: &InjectionGraph) -> Result<()> {
+    let json = serde_json::to_string_pretty(injection)?;
+    writeln!(out, "{}", json)?;
+    Ok(())
+}
+
+pub fn output_project_model(out: &mut impl Write, model: &ProjectModel) -> Result<()> {
+    let json = serde_json::to_string_pretty(model)?;
+    writeln!(out, "{}", json)?;
     Ok(())
 }
@@ -1,128 +1,182 @@
 // src/formatter/text.rs
 use anyhow::Result;
+use std::io::Write;
 use colored::*;
 use std::path::Path;
 use crate::models::*;
 use crate::models::FileType;
 use crate::formatter::text::DirectoryNode;
 
-pub fn output_structure(structure: &ProjectStructure) -> Result<()> {
-    println!("\n{} Project Structure:", "STRUCTURE:".green().bold());
-    print_directory(&structure.root, 0, &structure.root.path);
-    println!();
-    output_summary_from_structure(&structure.root);
+pub fn output_structure(out: &mut impl Write, structure: &ProjectStructure) -> Result<()> {
+    writeln!(out, "\n{} Project Structure:", "STRUCTURE:".green().bold())?;
+    print_directory(out, &structure.root, 0)?;
+    writeln!(out)?;
+    output_summary_from_structure(out, &structure.root)?;
     Ok(())
 }
 
-pub fn output_components(components: &[Component], detailed: bool) -> Result<()> {
-    println!("\n{} Components ({}):", "COMPONENTS:".green().bold(), components.len());
+pub fn output_components(out: &mut impl Write, components: &[Component], detailed: bool) -> Result<()> {
+    writeln!(out, "\n{} Components ({}):", "COMPONENTS:".green().bold(), components.len())?;
 
     if components.is_empty() {
-        println!("  No components found");
+        writeln!(out, "  No components found")?;
         return Ok(());
     }
 
     for component in components {
-        println!("  {} ({})", component.name.yellow(), component.path.display());
+        let standalone = if component.standalone { " [standalone]".cyan() } else { "".normal() };
+        writeln!(out, "  {}{} ({})", component.name.yellow(), standalone, component.path.display())?;
 
         if detailed {
+            if let Some(ref project) = component.project_info.project {
+                writeln!(out, "    Project: {}", project)?;
+            }
+
+            if !component.project_info.tags.is_empty() {
+                writeln!(out, "    Tags: {}", component.project_info.tags.join(", "))?;
+            }
+
             if let Some(ref selector) = component.selector {
-                println!("    Selector: {}", selector);
+                writeln!(out, "    Selector: {}", selector)?;
+            }
+
+            if !component.imports.is_empty() {
+                writeln!(out, "    Imports: {}", component.imports.join(", "))?;
             }
 
-            if let Some(ref template_path) = component.template_path {
-                println!("    Template: {}", template_path.display());
+            if !component.providers.is_empty() {
+                writeln!(out, "    Providers: {}", component.providers.join(", "))?;
             }
 
-            if !component.style_paths.is_empty() {
-                println!("    Styles:");
+            if !component.host_directives.is_empty() {
+                writeln!(out, "    Host directives: {}", component.host_directives.join(", "))?;
+            }
+
+            if !component.schemas.is_empty() {
+                writeln!(out, "    Schemas: {}", component.schemas.join(", "))?;
+            }
+
+            match (component.template_kind, &component.template_path) {
+                (Some(TemplateKind::External), Some(template_path)) => {
+                    writeln!(out, "    Template: {}", template_path.display())?;
+                }
+                (Some(TemplateKind::Inline), _) => writeln!(out, "    Template: (inline)")?,
+                _ => {}
+            }
+
+            if !component.style_paths.is_empty() || component.inline_styles > 0 {
+                writeln!(out, "    Styles:")?;
                 for style_path in &component.style_paths {
-                    println!("      {}", style_path.display());
+                    writeln!(out, "      {}", style_path.display())?;
+                }
+                if component.inline_styles > 0 {
+                    writeln!(out, "      ({} inline)", component.inline_styles)?;
                 }
             }
 
             if let Some(ref test_path) = component.test_path {
-                println!("    Test: {}", test_path.display());
+                writeln!(out, "    Test: {}", test_path.display())?;
             }
 
-            println!();
+            print_component_api(out, &component.api)?;
+
+            writeln!(out)?;
         }
     }
 
+    let standalone_count = components.iter().filter(|c| c.standalone).count();
+    writeln!(out, "\n  Standalone: {} of {}", standalone_count, components.len())?;
+
     Ok(())
 }
 
-pub fn output_services(services: &[Service], detailed: bool) -> Result<()> {
-    println!("\n{} Services ({}):", "SERVICES:".green().bold(), services.len());
+pub fn output_services(out: &mut impl Write, services: &[Service], detailed: bool) -> Result<()> {
+    writeln!(out, "\n{} Services ({}):", "SERVICES:".green().bold(), services.len())?;
 
     if services.is_empty() {
-        println!("  No services found");
+        writeln!(out, "  No services found")?;
         return Ok(());
     }
 
     for service in services {
-        println!("  {} ({})", service.name.yellow(), service.path.display());
+        writeln!(out, "  {} ({})", service.name.yellow(), service.path.display())?;
 
         if detailed {
+            if let Some(ref project) = service.project_info.project {
+                writeln!(out, "    Project: {}", project)?;
+            }
+
+            if !service.project_info.tags.is_empty() {
+                writeln!(out, "    Tags: {}", service.project_info.tags.join(", "))?;
+            }
+
             if let Some(ref scope) = service.injectable_scope {
-                println!("    Injectable scope: {}", scope);
+                writeln!(out, "    Injectable scope: {}", scope)?;
             }
 
             if let Some(ref test_path) = service.test_path {
-                println!("    Test: {}", test_path.display());
+                writeln!(out, "    Test: {}", test_path.display())?;
             }
 
-            println!();
+            writeln!(out)?;
         }
     }
 
     Ok(())
 }
 
-pub fn output_modules(modules: &[Module], detailed: bool) -> Result<()> {
-    println!("\n{} Modules ({}):", "MODULES:".green().bold(), modules.len());
+pub fn output_modules(out: &mut impl Write, modules: &[Module], detailed: bool) -> Result<()> {
+    writeln!(out, "\n{} Modules ({}):", "MODULES:".green().bold(), modules.len())?;
 
     if modules.is_empty() {
-        println!("  No modules found");
+        writeln!(out, "  No modules found")?;
         return Ok(());
     }
 
     for module in modules {
-        println!("  {} ({})", module.name.yellow(), module.path.display());
+        writeln!(out, "  {} ({})", module.name.yellow(), module.path.display())?;
 
         if detailed {
+            if let Some(ref project) = module.project_info.project {
+                writeln!(out, "    Project: {}", project)?;
+            }
+
+            if !module.project_info.tags.is_empty() {
+                writeln!(out, "    Tags: {}", module.project_info.tags.join(", "))?;
+            }
+
             if !module.declarations.is_empty() {
-                println!("    Declarations: {}", module.declarations.join(", "));
+                writeln!(out, "    Declarations: {}", module.declarations.join(", "))?;
             }
 
             if !module.imports.is_empty() {
-                println!("    Imports: {}", module.imports.join(", "));
+                writeln!(out, "    Imports: {}", module.imports.join(", "))?;
             }
 
             if !module.exports.is_empty() {
-                println!("    Exports: {}", module.exports.join(", "));
+                writeln!(out, "    Exports: {}", module.exports.join(", "))?;
             }
 
             if !module.providers.is_empty() {
-                println!("    Providers: {}", module.providers.join(", "));
+                writeln!(out, "    Providers: {}", module.providers.join(", "))?;
             }
 
             if !module.bootstrap.is_empty() {
-                println!("    Bootstrap: {}", module.bootstrap.join(", "));
+                writeln!(out, "    Bootstrap: {}", module.bootstrap.join(", "))?;
             }
 
-            println!();
+            writeln!(out)?;
         }
     }
 
     Ok(())
 }
 
-pub fn output_dependencies(dependencies: &[Dependency]) -> Result<()> {
-    println!("\n{} Dependencies ({}):", "DEPENDENCIES:".green().bold(), dependencies.len());
+pub fn output_dependencies(out: &mut impl Write, dependencies: &[Dependency]) -> Result<()> {
+    writeln!(out, "\n{} Dependencies ({}):", "DEPENDENCIES:".green().bold(), dependencies.len())?;
 
     if dependencies.is_empty() {
-        println!("  No dependencies found");
+        writeln!(out, "  No dependencies found")?;
         return Ok(());
     }
 
@@ -132,7 +186,7 @@ pub fn output_dependencies(dependencies: &[Dependency]) -> Result<()> {
     for dep in dependencies {
         let source = dep.source.display().to_string();
         let entry = grouped.entry(source).or_insert_with(Vec::new);
-        entry.push((dep.target.clone(), &dep.import_type));
+        entry.push(dep);
     }
 
     // Sort sources
@@ -140,11 +194,11 @@ pub fn output_dependencies(dependencies: &[Dependency]) -> Result<()> {
     sources.sort();
 
     for source in sources {
-        println!("  {}:", source);
+        writeln!(out, "  {}:", source)?;
 
         let deps = &grouped[source];
-        for (target, import_type) in deps {
-            let type_str = match import_type {
+        for dep in deps {
+            let type_str = match dep.import_type {
                 ImportType::Component => "Component".cyan(),
                 ImportType::Service => "Service".green(),
                 ImportType::Module => "Module".yellow(),
@@ -156,35 +210,327 @@ pub fn output_dependencies(dependencies: &[Dependency]) -> Result<()> {
                 ImportType::Other => "Other".normal(),
             };
 
-            println!("    {} -> {}", target, type_str);
+            let target_str = match dep.target {
+                DependencyTarget::File(_) => dep.target.to_string().normal(),
+                DependencyTarget::Package(_) => dep.target.to_string().blue(),
+                DependencyTarget::Unresolved(_) => dep.target.to_string().red(),
+            };
+
+            let names: Vec<String> = dep
+                .names
+                .iter()
+                .map(|n| match n.alias {
+                    Some(ref alias) => format!("{} as {}", n.name, alias),
+                    None => n.name.clone(),
+                })
+                .collect();
+            let names_str = if names.is_empty() {
+                String::new()
+            } else {
+                format!(" {{ {} }}", names.join(", "))
+            };
+
+            let mut markers = Vec::new();
+            match dep.kind {
+                DependencyKind::ReExport => markers.push("[re-export]"),
+                DependencyKind::Require => markers.push("[require]"),
+                DependencyKind::Import => {}
+            }
+            if dep.dynamic {
+                markers.push("[dynamic]");
+            }
+            if dep.type_only {
+                markers.push("[type]");
+            }
+            let markers_str = if markers.is_empty() {
+                String::new()
+            } else {
+                format!(" {}", markers.join(" ").dimmed())
+            };
+
+            writeln!(out, "    {} -> {}{}{}", target_str, type_str, names_str, markers_str)?;
+        }
+
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+pub fn output_cycles(out: &mut impl Write, cycles: &[Cycle]) -> Result<()> {
+    writeln!(out, "\n{} Circular dependencies ({}):", "CYCLES:".green().bold(), cycles.len())?;
+
+    if cycles.is_empty() {
+        writeln!(out, "  No circular dependencies found")?;
+        return Ok(());
+    }
+
+    for (i, cycle) in cycles.iter().enumerate() {
+        // Close the chain by repeating its first file
+        let chain: Vec<String> = cycle
+            .chain
+            .iter()
+            .chain(cycle.chain.first())
+            .map(|path| path.display().to_string())
+            .collect();
+        writeln!(out, "  {}. {}", i + 1, chain.join(&format!(" {} ", "→".red())))?;
+
+        if cycle.files.len() > cycle.chain.len() {
+            writeln!(out, "     {} files take part in this cycle:", cycle.files.len())?;
+            for file in &cycle.files {
+                writeln!(out, "       - {}", file.display())?;
+            }
         }
+    }
+
+    Ok(())
+}
+
+pub fn output_boundary_violations(out: &mut impl Write, violations: &[BoundaryViolation]) -> Result<()> {
+    writeln!(out, "\n{} Boundary violations ({}):", "BOUNDARIES:".green().bold(), violations.len())?;
+
+    if violations.is_empty() {
+        writeln!(out, "  No boundary violations found")?;
+        return Ok(());
+    }
+
+    for violation in violations {
+        writeln!(out, 
+            "  {}:{} {} -> {} ({})",
+            violation.source.display(),
+            violation.line,
+            violation.source_project.yellow(),
+            violation.target_project.yellow(),
+            violation.specifier
+        )?;
+        writeln!(out, "    {}", violation.reason.red())?;
+    }
+
+    Ok(())
+}
+
+pub fn output_architecture_violations(out: &mut impl Write, violations: &[ArchitectureViolation]) -> Result<()> {
+    writeln!(out, "\n{} Architecture violations ({}):", "ARCHITECTURE:".green().bold(), violations.len())?;
+
+    if violations.is_empty() {
+        writeln!(out, "  No architecture violations found")?;
+        return Ok(());
+    }
+
+    // Group violations by rule
+    let mut grouped: std::collections::BTreeMap<&str, Vec<&ArchitectureViolation>> = std::collections::BTreeMap::new();
+    for violation in violations {
+        grouped.entry(&violation.rule).or_default().push(violation);
+    }
 
-        println!();
+    for (rule, violations) in grouped {
+        writeln!(out, "  {} ({}):", rule.yellow(), violations.len())?;
+        for violation in violations {
+            writeln!(out, 
+                "    {}:{} [{}] -> {} [{}]",
+                violation.source.display(),
+                violation.line,
+                violation.source_layer.cyan(),
+                violation.target.display(),
+                violation.target_layer.red()
+            )?;
+        }
     }
 
     Ok(())
 }
 
-pub fn output_routes(routes: &[Route]) -> Result<()> {
-    println!("\n{} Routes ({}):", "ROUTES:".green().bold(), routes.len());
+pub fn output_routes(out: &mut impl Write, routes: &[Route]) -> Result<()> {
+    writeln!(out, "\n{} Routes ({}):", "ROUTES:".green().bold(), routes.len())?;
 
     if routes.is_empty() {
-        println!("  No routes found");
+        writeln!(out, "  No routes found")?;
         return Ok(());
     }
 
     for route in routes {
-        print_route(route, 1);
+        print_route(out, route, 1)?;
+    }
+
+    // Every URL behind a guard, with the guards inherited from its parents
+    let mut protected = Vec::new();
+    for route in routes {
+        collect_protected_routes(route, &[], &mut protected);
+    }
+
+    if !protected.is_empty() {
+        writeln!(out, "\n{} Protected URLs ({}):", "GUARDS:".green().bold(), protected.len())?;
+        for (full_path, guards) in protected {
+            writeln!(out, "  {} <- {}", full_path.green(), guards.join(", ").red())?;
+        }
     }
 
     Ok(())
 }
 
-fn print_directory(dir: &DirectoryNode, depth: usize, base_path: &Path) {
+pub fn output_route_findings(out: &mut impl Write, findings: &[RouteFinding]) -> Result<()> {
+    writeln!(out, "\n{} Route findings ({}):", "AUDIT:".green().bold(), findings.len())?;
+
+    if findings.is_empty() {
+        writeln!(out, "  No problems found")?;
+        return Ok(());
+    }
+
+    for finding in findings {
+        let kind = match finding.kind {
+            RouteFindingKind::DuplicatePath => "duplicate".yellow(),
+            RouteFindingKind::BrokenRedirect => "broken-redirect".red(),
+            RouteFindingKind::Unreachable => "unreachable".yellow(),
+            RouteFindingKind::UnknownComponent => "unknown-component".red(),
+            RouteFindingKind::UnresolvedLazyTarget => "unresolved-lazy".red(),
+        };
+        writeln!(out, "  {}:{} [{}] {}", finding.file.display(), finding.line, kind, finding.message)?;
+    }
+
+    Ok(())
+}
+
+pub fn output_render_tree(out: &mut impl Write, tree: &RenderTree) -> Result<()> {
+    writeln!(out, "\n{} Render Tree ({} usages):", "TEMPLATES:".green().bold(), tree.edges.len())?;
+
+    if tree.roots.is_empty() {
+        writeln!(out, "  No components found")?;
+        return Ok(());
+    }
+
+    for root in &tree.roots {
+        print_render_node(out, root, 1)?;
+    }
+
+    Ok(())
+}
+
+pub fn output_injection(out: &mut impl Write, injection: &InjectionGraph) -> Result<()> {
+    writeln!(out, "\n{} Injections ({}):", "DI:".green().bold(), injection.edges.len())?;
+
+    if injection.edges.is_empty() {
+        writeln!(out, "  No injections found")?;
+    }
+
+    // Group injections by consumer
+    let mut grouped: std::collections::BTreeMap<(&str, &Path), Vec<&InjectionEdge>> = std::collections::BTreeMap::new();
+    for edge in &injection.edges {
+        grouped.entry((&edge.consumer, &edge.consumer_path)).or_default().push(edge);
+    }
+
+    for ((consumer, path), edges) in grouped {
+        writeln!(out, "  {} [{:?}] ({})", consumer.yellow(), edges[0].consumer_kind, path.display())?;
+
+        for edge in edges {
+            let site = match edge.site {
+                InjectionSite::Constructor => "constructor",
+                InjectionSite::InjectFunction => "inject()",
+            };
+            let optional = if edge.optional { ", optional" } else { "" };
+            let provider = match edge.provider {
+                TokenSource::Project(ref path) => path.display().to_string().normal(),
+                TokenSource::Package(ref package) => package.blue(),
+                TokenSource::Unresolved => "unresolved".red(),
+            };
+            writeln!(out, "    <- {} [{}{}] ({})", edge.token.cyan(), site, optional, provider)?;
+        }
+
+        writeln!(out)?;
+    }
+
+    if !injection.injection_tokens.is_empty() {
+        writeln!(out, "  {}", "InjectionTokens:".bold())?;
+        for token in &injection.injection_tokens {
+            let type_name = token.type_name.as_deref().unwrap_or("unknown");
+            writeln!(out, "    {}<{}> ({})", token.name.yellow(), type_name, token.path.display())?;
+            if token.consumers.is_empty() {
+                writeln!(out, "      Not injected anywhere")?;
+            } else {
+                writeln!(out, "      Used by: {}", token.consumers.join(", "))?;
+            }
+        }
+        writeln!(out)?;
+    }
+
+    if !injection.unresolved.is_empty() {
+        writeln!(out, "  {}", "Unresolved tokens:".red().bold())?;
+        for token in &injection.unresolved {
+            writeln!(out, "    {}", token)?;
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+fn print_render_node(out: &mut impl Write, node: &RenderNode, depth: usize) -> Result<()> {
+    let indent = "  ".repeat(depth);
+    let name = match node.kind {
+        RenderKind::Component => node.name.yellow(),
+        RenderKind::Directive => node.name.magenta(),
+    };
+
+    if node.recursive {
+        writeln!(out, "{}{} {}", indent, name, "(recursive)".red())?;
+        return Ok(());
+    }
+
+    writeln!(out, "{}{}", indent, name)?;
+
+    for child in &node.children {
+        print_render_node(out, child, depth + 1)?;
+    }
+
+    Ok(())
+}
+
+fn print_component_api(out: &mut impl Write, api: &[ApiMember]) -> Result<()> {
+    let sections = [
+        ("Inputs", &[ApiKind::Input][..]),
+        ("Outputs", &[ApiKind::Output][..]),
+        ("Models", &[ApiKind::Model][..]),
+        (
+            "Queries",
+            &[ApiKind::ViewChild, ApiKind::ViewChildren, ApiKind::ContentChild, ApiKind::ContentChildren][..],
+        ),
+    ];
+
+    for (title, kinds) in sections {
+        let members: Vec<_> = api.iter().filter(|m| kinds.contains(&m.kind)).collect();
+        if members.is_empty() {
+            continue;
+        }
+
+        writeln!(out, "    {}:", title)?;
+        for member in members {
+            let mut line = format!("      {}", member.name.cyan());
+            if let Some(ref type_name) = member.type_name {
+                line.push_str(&format!(": {}", type_name));
+            }
+            if let Some(ref alias) = member.alias {
+                line.push_str(&format!(" (alias: {})", alias));
+            }
+            if member.required {
+                line.push_str(&format!(" {}", "required".red()));
+            }
+            if title == "Queries" {
+                line.push_str(&format!(" [{:?}]", member.kind));
+            }
+            if member.signal {
+                line.push_str(&format!(" {}", "signal".magenta()));
+            }
+            writeln!(out, "{}", line)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn print_directory(out: &mut impl Write, dir: &DirectoryNode, depth: usize) -> Result<()> {
     let indent = "  ".repeat(depth);
     let name = if depth == 0 { dir.name.clone() } else { format!("{}/", dir.name) };
 
-    println!("{}{}{}", indent, name.blue().bold(), "");
+    writeln!(out, "{}{}", indent, name.blue().bold())?;
 
     // Print files
     for file in &dir.files {
@@ -216,33 +562,94 @@ fn print_directory(dir: &DirectoryNode, depth: usize, base_path: &Path) {
             FileType::Other => "O".normal(),
         };
 
-        println!("{}  [{}] {}", indent, file_type_indicator, file.name);
+        writeln!(out, "{}  [{}] {}", indent, file_type_indicator, file.name)?;
     }
 
     // Print subdirectories
     for subdir in &dir.directories {
-        print_directory(subdir, depth + 1, base_path);
+        print_directory(out, subdir, depth + 1)?;
     }
+
+    Ok(())
 }
 
-fn print_route(route: &Route, depth: usize) {
+fn print_route(out: &mut impl Write, route: &Route, depth: usize) -> Result<()> {
     let indent = "  ".repeat(depth);
-    let path = if route.path.is_empty() { "/" } else { &route.path };
 
-    print!("{}{}", indent, path.green());
+    write!(out, "{}{}", indent, route.full_path.green())?;
 
     if let Some(ref component) = route.component {
-        print!(" -> {}", component.yellow());
+        write!(out, " -> {}", component.yellow())?;
     }
 
     if let Some(ref lazy_module) = route.lazy_module {
-        print!(" (lazy: {})", lazy_module.cyan());
+        write!(out, " (lazy: {})", lazy_module.cyan())?;
+    }
+
+    if let Some(ref lazy_component) = route.lazy_component {
+        write!(out, " (lazy component: {})", lazy_component.cyan())?;
+    }
+
+    if let Some(ref redirect_to) = route.redirect_to {
+        write!(out, " => '{}'", redirect_to.green())?;
+        if let Some(ref path_match) = route.path_match {
+            write!(out, " (pathMatch: {})", path_match)?;
+        }
     }
 
-    println!();
+    if let Some(ref outlet) = route.outlet {
+        write!(out, " [outlet: {}]", outlet)?;
+    }
 
+    if let Some(ref title) = route.title {
+        write!(out, " \"{}\"", title)?;
+    }
+
+    for (label, guards) in [
+        ("canActivate", &route.can_activate),
+        ("canActivateChild", &route.can_activate_child),
+        ("canDeactivate", &route.can_deactivate),
+        ("canMatch", &route.can_match),
+    ] {
+        if !guards.is_empty() {
+            write!(out, " [{}: {}]", label, guards.join(", ").red())?;
+        }
+    }
+
+    if !route.resolve.is_empty() {
+        let resolvers: Vec<String> = route.resolve.iter().map(|(key, resolver)| format!("{}={}", key, resolver)).collect();
+        write!(out, " [resolve: {}]", resolvers.join(", "))?;
+    }
+
+    writeln!(out)?;
+
+    for child in &route.children {
+        print_route(out, child, depth + 1)?;
+    }
+
+    Ok(())
+}
+
+fn collect_protected_routes(route: &Route, inherited: &[String], out: &mut Vec<(String, Vec<String>)>) {
+    let mut guards = inherited.to_vec();
+    for guard in route.can_match.iter().chain(&route.can_activate) {
+        if !guards.contains(guard) {
+            guards.push(guard.clone());
+        }
+    }
+
+    if !guards.is_empty() {
+        out.push((route.full_path.clone(), guards.clone()));
+    }
+
+    // `canActivateChild` only runs for the children
+    for guard in &route.can_activate_child {
+        if !guards.contains(guard) {
+            guards.push(guard.clone());
+        }
+    }
     for child in &route.children {
-        print_route(child, depth + 1);
+        collect_protected_routes(child, &guards, out);
     }
 }
 
@@ -255,7 +662,7 @@ fn collect_all_files(dir: &DirectoryNode, out: &mut Vec<FileType>) {
     }
 }
 
-fn output_summary_from_structure(root: &DirectoryNode) {
+fn output_summary_from_structure(out: &mut impl Write, root: &DirectoryNode) -> Result<()> {
     let mut types = Vec::new();
     collect_all_files(root, &mut types);
 
@@ -267,12 +674,14 @@ fn output_summary_from_structure(root: &DirectoryNode) {
     }
 
     // 出力
-    println!("\n{} Summary:", "SUMMARY:".green().bold());
+    writeln!(out, "\n{} Summary:", "SUMMARY:".green().bold())?;
     for (file_type, cnt) in &counts {
         // FileType を文字列に変換するメソッドがあれば使ってください
-        println!("  {:<15} {}", format!("{:?}", file_type), cnt);
+        writeln!(out, "  {:<15} {}", format!("{:?}", file_type), cnt)?;
     }
     let total: usize = counts.values().sum();
-    println!("  {:<15} {}", "Total", total);
-    println!();
+    writeln!(out, "  {:<15} {}", "Total", total)?;
+    writeln!(out)?;
+
+    Ok(())
 }
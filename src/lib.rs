@@ -0,0 +1,27 @@
+// src/lib.rs
+//
+// Angular project analysis as a library. `Analyzer` reads a project into the
+// `models` types without printing anything; the formatters render those
+// models to any writer.
+
+pub mod config;
+pub mod formatter;
+pub mod models;
+
+mod analyzer;
+mod architecture;
+mod boundaries;
+mod cache;
+mod cycles;
+mod links;
+mod resolver;
+mod route_audit;
+mod scanner;
+mod template;
+mod tsconfig;
+mod typescript;
+mod utils;
+mod workspace;
+
+pub use analyzer::{ Analyzer, AnalyzerOptions };
+pub use config::Config;
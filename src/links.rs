@@ -0,0 +1,104 @@
+// src/links.rs
+//
+// Cross-references for the whole project model: for each class, the modules
+// and components that declare, import, export or provide it, the routes that
+// render it and the files that import it.
+
+use std::collections::{ BTreeSet, HashMap };
+use std::path::Path;
+
+use crate::models::{ ClassKind, ClassLinks, DependencyTarget, ProjectModel, Route };
+use crate::resolver::ModuleResolver;
+
+pub fn link_classes(model: &ProjectModel, resolver: &ModuleResolver) -> Vec<ClassLinks> {
+    let classes = model.components
+        .iter()
+        .map(|c| (&c.name, ClassKind::Component, &c.path))
+        .chain(model.directives.iter().map(|d| (&d.name, ClassKind::Directive, &d.path)))
+        .chain(model.pipes.iter().map(|p| (&p.name, ClassKind::Pipe, &p.path)))
+        .chain(model.services.iter().map(|s| (&s.name, ClassKind::Service, &s.path)))
+        .chain(model.modules.iter().map(|m| (&m.name, ClassKind::Module, &m.path)));
+
+    let mut routed = Vec::new();
+    collect_routed_components(&model.routes, &mut routed);
+
+    let mut importers: HashMap<&Path, BTreeSet<&Path>> = HashMap::new();
+    for dep in &model.dependencies {
+        if let DependencyTarget::File(ref target) = dep.target {
+            importers.entry(target).or_default().insert(&dep.source);
+        }
+    }
+
+    classes.map(|(name, kind, path)| link_class(model, name, kind, path, &routed, &importers, resolver)).collect()
+}
+
+fn link_class(
+    model: &ProjectModel,
+    name: &str,
+    kind: ClassKind,
+    path: &Path,
+    routed: &[(&str, &str)],
+    importers: &HashMap<&Path, BTreeSet<&Path>>,
+    resolver: &ModuleResolver
+) -> ClassLinks {
+    let path = resolver.relative_path(path);
+    let lists = |list: &[String]| list.iter().any(|item| item == name);
+
+    let mut links = ClassLinks {
+        name: name.to_string(),
+        kind,
+        path: path.clone(),
+        declared_in: Vec::new(),
+        imported_in: Vec::new(),
+        exported_from: Vec::new(),
+        provided_in: Vec::new(),
+        routes: Vec::new(),
+        imported_by: Vec::new(),
+    };
+
+    for module in &model.modules {
+        if lists(&module.declarations) {
+            links.declared_in.push(module.name.clone());
+        }
+        if lists(&module.imports) {
+            links.imported_in.push(module.name.clone());
+        }
+        if lists(&module.exports) {
+            links.exported_from.push(module.name.clone());
+        }
+        if lists(&module.providers) {
+            links.provided_in.push(module.name.clone());
+        }
+    }
+
+    for component in &model.components {
+        if lists(&component.imports) {
+            links.imported_in.push(component.name.clone());
+        }
+        if lists(&component.providers) {
+            links.provided_in.push(component.name.clone());
+        }
+    }
+
+    let routes: BTreeSet<&str> = routed.iter().filter(|(component, _)| *component == name).map(|(_, url)| *url).collect();
+    links.routes = routes.into_iter().map(str::to_string).collect();
+
+    links.imported_by = importers
+        .get(path.as_path())
+        .map(|files| files.iter().map(|file| file.to_path_buf()).collect())
+        .unwrap_or_default();
+
+    links
+}
+
+// (component, full path) for every route with a `component` or a named
+// `loadComponent` export
+fn collect_routed_components<'a>(routes: &'a [Route], out: &mut Vec<(&'a str, &'a str)>) {
+    for route in routes {
+        let lazy_export = route.lazy_component.as_deref().and_then(|target| target.split_once('#')).map(|(_, export)| export);
+        for component in route.component.as_deref().into_iter().chain(lazy_export) {
+            out.push((component, &route.full_path));
+        }
+        collect_routed_components(&route.children, out);
+    }
+}
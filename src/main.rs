@@ -2,13 +2,18 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use colored::*;
+use console::Term;
+use notify_debouncer_full::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{new_debouncer, Debouncer, RecommendedCache};
+use std::collections::BTreeSet;
+use std::io::{self, Write};
 use std::process;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
 
-mod analyzer;
-mod formatter;
-mod models;
-mod utils;
+use angular_structure_analyzer::config::{ColorChoice, CommandDefaults, Config, CONFIG_FILE};
+use angular_structure_analyzer::{formatter, Analyzer, AnalyzerOptions};
 use colored::control;
 use atty::Stream;
 
@@ -23,59 +28,127 @@ struct Cli {
     command: Option<Commands>,
 
     /// Include test files in the analysis
-    #[arg(short = 't', long)]
-    include_tests: bool,
+    #[arg(short = 't', long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    include_tests: Option<bool>,
 
     /// Include styles files in the analysis
-    #[arg(short = 's', long)]
-    include_styles: bool,
+    #[arg(short = 's', long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    include_styles: Option<bool>,
 
     /// Include node_modules in the analysis (not recommended)
-    #[arg(long)]
-    include_node_modules: bool,
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    include_node_modules: Option<bool>,
 
-    /// Maximum depth of the directory tree to display
-    #[arg(short, long, default_value_t = 10)]
-    max_depth: usize,
+    /// Maximum directory depth to analyze, for every subcommand (unlimited by default)
+    #[arg(short, long)]
+    max_depth: Option<usize>,
 
     /// JSON output format
+    #[arg(short, long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    json: Option<bool>,
+
+    /// When to color the output [default: auto]
+    #[arg(long, value_enum)]
+    color: Option<ColorChoice>,
+
+    /// Skip files and directories matching this glob, relative to the
+    /// analyzed path (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<String>,
+
+    /// Only analyze this workspace project from angular.json or an Nx
+    /// project.json (all projects by default)
     #[arg(short, long)]
-    json: bool,
+    project: Option<String>,
+
+    /// Number of threads to parse files on (one per CPU by default)
+    #[arg(short = 'J', long)]
+    jobs: Option<usize>,
+
+    /// Directory of the parse cache [default: .angular/ngstructure-cache]
+    #[arg(long, value_name = "PATH")]
+    cache_dir: Option<PathBuf>,
+
+    /// Parse every file again without reading or writing the cache
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Delete the parse cache before analyzing
+    #[arg(long)]
+    clear_cache: bool,
+
+    /// Keep running and print the output again whenever a project file changes
+    #[arg(short, long)]
+    watch: bool,
+
+    /// Read settings from this file instead of the nearest .ngstructure.toml
+    #[arg(long, conflicts_with = "no_config")]
+    config: Option<PathBuf>,
+
+    /// Ignore .ngstructure.toml files
+    #[arg(long)]
+    no_config: bool,
 }
 
-#[derive(Subcommand)]
+#[derive(Subcommand, Clone, Copy)]
 enum Commands {
     /// Analyze components in the project
     Components {
         /// Show detailed information about each component
-        #[arg(short, long)]
-        detailed: bool,
+        #[arg(short, long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+        detailed: Option<bool>,
     },
     /// Analyze services in the project
     Services {
         /// Show detailed information about each service
-        #[arg(short, long)]
-        detailed: bool,
+        #[arg(short, long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+        detailed: Option<bool>,
     },
     /// Analyze modules in the project
     Modules {
         /// Show detailed information about each module
-        #[arg(short, long)]
-        detailed: bool,
+        #[arg(short, long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+        detailed: Option<bool>,
     },
     /// Analyze dependencies between files
     Dependencies {
         /// Show dependencies as a graph
-        #[arg(short, long)]
-        graph: bool,
+        #[arg(short, long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+        graph: Option<bool>,
     },
+    /// Find circular imports between files (exits with 1 when any are found)
+    Cycles {},
+    /// Check imports between workspace projects against Nx tag constraints
+    /// (exits with 1 when any are violated)
+    Boundaries {},
+    /// Check imports against the layer rules in .ngstructure.toml
+    /// (exits with 1 when any are violated)
+    CheckArchitecture {},
     /// Analyze routes in the project
-    Routes {},
+    Routes {
+        /// Check routes for duplicates, broken redirects, unreachable routes,
+        /// unknown components and unresolved lazy imports (exits with 1 on findings)
+        #[arg(short, long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+        audit: Option<bool>,
+    },
+    /// Analyze everything in one pass and print the project model, with the
+    /// links between classes, as JSON
+    All {},
+    /// Show which classes and functions inject which services and tokens
+    Di {
+        /// Show the injection graph as a graph
+        #[arg(short, long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+        graph: Option<bool>,
+    },
+    /// Show which components render which components and directives in their templates
+    RenderTree {
+        /// Show the render tree as a graph
+        #[arg(short, long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+        graph: Option<bool>,
+    },
 }
 
 fn main() -> Result<()> {
-    let use_color = atty::is(Stream::Stdout);
-    control::set_override(use_color);
     let cli = Cli::parse();
     
     // Set up the path to analyze
@@ -85,6 +158,25 @@ fn main() -> Result<()> {
         eprintln!("{} Path does not exist: {}", "ERROR:".red().bold(), path.display());
         process::exit(1);
     }
+
+    // Settings from .ngstructure.toml, which the flags above override
+    let config = match load_config(&cli, &path) {
+        Ok(config) => config.unwrap_or_default(),
+        Err(err) => {
+            eprintln!("{} {:#}", "ERROR:".red().bold(), err);
+            process::exit(1);
+        }
+    };
+
+    set_color(&cli, &config);
+    let json = cli.json.or(config.output.json).unwrap_or(false);
+
+    if let Some(jobs) = cli.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+            .context("Failed to start the thread pool")?;
+    }
     
     // Check if this is an Angular project
     if !is_angular_project(&path) {
@@ -92,71 +184,372 @@ fn main() -> Result<()> {
         eprintln!("Continuing anyway, but results may not be accurate.");
     }
     
-    // Create the analyzer with the specified options
-    let mut analyzer = analyzer::Analyzer::new(
-        path,
-        cli.include_tests,
-        cli.include_styles,
-        false,
-        cli.max_depth,
-    );
-    
+    if cli.clear_cache
+        && let Err(err) = Analyzer::clear_cache(&cache_dir(&cli, &path, &config))
+    {
+        eprintln!("{} {:#}", "ERROR:".red().bold(), err);
+        process::exit(1);
+    }
+
+    let mut analyzer = match build_analyzer(&cli, &path, &config) {
+        Ok(analyzer) => analyzer,
+        Err(err) => {
+            eprintln!("{} {:#}", "ERROR:".red().bold(), err);
+            process::exit(1);
+        }
+    };
+
+    if cli.watch {
+        return watch_project(&cli, &path, analyzer, config);
+    }
+
+    let mut out = io::stdout().lock();
+    if run(cli.command, &mut analyzer, &mut out, json, &config)? {
+        out.flush()?;
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
+// The analyzer for `path`, with the flags applied over the settings of `config`
+fn build_analyzer(cli: &Cli, path: &Path, config: &Config) -> Result<Analyzer> {
+    let mut analyzer = Analyzer::new(path.to_path_buf(), AnalyzerOptions {
+        include_tests: cli.include_tests.or(config.include_tests).unwrap_or(false),
+        include_styles: cli.include_styles.or(config.include_styles).unwrap_or(false),
+        include_node_modules: cli.include_node_modules.or(config.include_node_modules).unwrap_or(false),
+        max_depth: cli.max_depth.or(config.max_depth),
+    });
+
+    analyzer.apply_config(config.clone())?;
+    analyzer.exclude(&cli.exclude)?;
+
+    if let Some(ref project) = cli.project.clone().or(config.project.clone()) {
+        analyzer.select_project(project)?;
+    }
+
+    if !cli.no_cache {
+        analyzer.use_cache(&cache_dir(cli, path, config));
+    }
+    analyzer.show_progress(true);
+
+    Ok(analyzer)
+}
+
+fn cache_dir(cli: &Cli, path: &Path, config: &Config) -> PathBuf {
+    cli.cache_dir
+        .clone()
+        .or_else(|| config.cache_dir.as_ref().map(|dir| config.dir.join(dir)))
+        .unwrap_or_else(|| Analyzer::default_cache_dir(path))
+}
+
+fn set_color(cli: &Cli, config: &Config) {
+    let use_color = match cli.color.or(config.output.color).unwrap_or(ColorChoice::Auto) {
+        ColorChoice::Auto => atty::is(Stream::Stdout),
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+    };
+    control::set_override(use_color);
+}
+
+// Runs one subcommand and prints its output. Returns whether a checking
+// subcommand found anything.
+fn run(
+    command: Option<Commands>,
+    analyzer: &mut Analyzer,
+    out: &mut impl Write,
+    json: bool,
+    config: &Config
+) -> Result<bool> {
+    let command_defaults = |name: &str| config.command(name).unwrap_or(&DEFAULT_COMMAND);
+
     // Run the analyzer based on the command
-    match cli.command {
+    let mut found = false;
+    match command {
         Some(Commands::Components { detailed }) => {
+            let detailed = detailed.or(command_defaults("components").detailed).unwrap_or(false);
             let components = analyzer.analyze_components().context("Failed to analyze components")?;
-            if cli.json {
-                formatter::json::output_components(&components)?;
+            if json {
+                formatter::json::output_components(out, &components)?;
             } else {
-                formatter::text::output_components(&components, detailed)?;
+                formatter::text::output_components(out, &components, detailed)?;
             }
         },
         Some(Commands::Services { detailed }) => {
+            let detailed = detailed.or(command_defaults("services").detailed).unwrap_or(false);
             let services = analyzer.analyze_services().context("Failed to analyze services")?;
-            if cli.json {
-                formatter::json::output_services(&services)?;
+            if json {
+                formatter::json::output_services(out, &services)?;
             } else {
-                formatter::text::output_services(&services, detailed)?;
+                formatter::text::output_services(out, &services, detailed)?;
             }
         },
         Some(Commands::Modules { detailed }) => {
+            let detailed = detailed.or(command_defaults("modules").detailed).unwrap_or(false);
             let modules = analyzer.analyze_modules().context("Failed to analyze modules")?;
-            if cli.json {
-                formatter::json::output_modules(&modules)?;
+            if json {
+                formatter::json::output_modules(out, &modules)?;
             } else {
-                formatter::text::output_modules(&modules, detailed)?;
+                formatter::text::output_modules(out, &modules, detailed)?;
             }
         },
         Some(Commands::Dependencies { graph }) => {
+            let graph = graph.or(command_defaults("dependencies").graph).unwrap_or(false);
             let dependencies = analyzer.analyze_dependencies().context("Failed to analyze dependencies")?;
-            if cli.json {
-                formatter::json::output_dependencies(&dependencies)?;
+            if json {
+                formatter::json::output_dependencies(out, &dependencies)?;
             } else if graph {
-                formatter::graph::output_dependencies(&dependencies)?;
+                formatter::graph::output_dependencies(out, &dependencies)?;
             } else {
-                formatter::text::output_dependencies(&dependencies)?;
+                formatter::text::output_dependencies(out, &dependencies)?;
             }
         },
-        Some(Commands::Routes {}) => {
+        Some(Commands::Cycles {}) => {
+            let cycles = analyzer.analyze_cycles().context("Failed to detect circular dependencies")?;
+            if json {
+                formatter::json::output_cycles(out, &cycles)?;
+            } else {
+                formatter::text::output_cycles(out, &cycles)?;
+            }
+            found = !cycles.is_empty();
+        },
+        Some(Commands::Boundaries {}) => {
+            let violations = analyzer.analyze_boundaries().context("Failed to check project boundaries")?;
+            if json {
+                formatter::json::output_boundary_violations(out, &violations)?;
+            } else {
+                formatter::text::output_boundary_violations(out, &violations)?;
+            }
+            found = !violations.is_empty();
+        },
+        Some(Commands::CheckArchitecture {}) => {
+            let violations = analyzer.check_architecture().context("Failed to check architecture")?;
+            if json {
+                formatter::json::output_architecture_violations(out, &violations)?;
+            } else {
+                formatter::text::output_architecture_violations(out, &violations)?;
+            }
+            found = !violations.is_empty();
+        },
+        Some(Commands::Routes { audit }) if audit.or(command_defaults("routes").audit).unwrap_or(false) => {
+            let findings = analyzer.audit_routes().context("Failed to audit routes")?;
+            if json {
+                formatter::json::output_route_findings(out, &findings)?;
+            } else {
+                formatter::text::output_route_findings(out, &findings)?;
+            }
+            found = !findings.is_empty();
+        },
+        Some(Commands::Routes { .. }) => {
             let routes = analyzer.analyze_routes().context("Failed to analyze routes")?;
-            if cli.json {
-                formatter::json::output_routes(&routes)?;
+            if json {
+                formatter::json::output_routes(out, &routes)?;
+            } else {
+                formatter::text::output_routes(out, &routes)?;
+            }
+        },
+        Some(Commands::All {}) => {
+            let model = analyzer.analyze_all().context("Failed to analyze project")?;
+            formatter::json::output_project_model(out, &model)?;
+        },
+        Some(Commands::RenderTree { graph }) => {
+            let graph = graph.or(command_defaults("render-tree").graph).unwrap_or(false);
+            let tree = analyzer.analyze_render_tree().context("Failed to analyze templates")?;
+            if json {
+                formatter::json::output_render_tree(out, &tree)?;
+            } else if graph {
+                formatter::graph::output_render_tree(out, &tree)?;
+            } else {
+                formatter::text::output_render_tree(out, &tree)?;
+            }
+        },
+        Some(Commands::Di { graph }) => {
+            let graph = graph.or(command_defaults("di").graph).unwrap_or(false);
+            let injection = analyzer.analyze_injection().context("Failed to analyze dependency injection")?;
+            if json {
+                formatter::json::output_injection(out, &injection)?;
+            } else if graph {
+                formatter::graph::output_injection(out, &injection)?;
             } else {
-                formatter::text::output_routes(&routes)?;
+                formatter::text::output_injection(out, &injection)?;
             }
         },
         None => {
             // Default command: show the full project structure
             let structure = analyzer.analyze_structure().context("Failed to analyze project structure")?;
-            if cli.json {
-                formatter::json::output_structure(&structure)?;
+            if json {
+                formatter::json::output_structure(out, &structure)?;
             } else {
-                formatter::text::output_structure(&structure)?;
+                formatter::text::output_structure(out, &structure)?;
             }
         },
     }
-    
-    Ok(())
+
+    Ok(found)
+}
+
+// Reruns the subcommand each time a project file is added, changed or
+// removed, until interrupted. Only the changed files are parsed again, and
+// only the directories the scan enters are watched, so node_modules, build
+// output and ignored directories cost nothing. A change to the config file
+// applies its settings from the next run on.
+fn watch_project(cli: &Cli, path: &Path, mut analyzer: Analyzer, mut config: Config) -> Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let mut debouncer = new_debouncer(Duration::from_millis(200), None, tx).context("Failed to start watching")?;
+    let mut watched = BTreeSet::new();
+
+    let terminal = Term::stdout();
+    loop {
+        if terminal.is_term() {
+            terminal.clear_screen()?;
+        }
+
+        let json = cli.json.or(config.output.json).unwrap_or(false);
+        let mut out = io::stdout().lock();
+        if let Err(err) = run(cli.command, &mut analyzer, &mut out, json, &config) {
+            eprintln!("{} {:#}", "ERROR:".red().bold(), err);
+        }
+        out.flush()?;
+        drop(out);
+
+        // The config file may live above the analyzed path
+        let mut dirs: BTreeSet<PathBuf> = analyzer.scanned_dirs().iter().map(absolute_path).collect();
+        if let Some(config_file) = config_file(cli, path, &config)
+            && let Some(dir) = config_file.parent()
+        {
+            dirs.insert(absolute_path(dir));
+        }
+        update_watches(&mut debouncer, &mut watched, dirs);
+
+        eprintln!("{} Watching '{}' for changes (Ctrl+C to stop)", "INFO:".blue().bold(), path.display());
+
+        // Reads (the analysis itself opens every file) and changes to
+        // ignored or excluded files are not worth a rerun
+        loop {
+            match rx.recv()? {
+                Ok(events) => {
+                    let changed: Vec<PathBuf> = events
+                        .into_iter()
+                        .filter(|event| !event.kind.is_access())
+                        .flat_map(|event| event.event.paths)
+                        .collect();
+                    if changed.is_empty() {
+                        continue;
+                    }
+
+                    if config_changed(cli, path, &config, &changed) {
+                        match reload_config(cli, path) {
+                            Ok((reloaded, rebuilt)) => {
+                                set_color(cli, &reloaded);
+                                config = reloaded;
+                                analyzer = rebuilt;
+                                break;
+                            },
+                            // The last output stays with the error below it
+                            // until the file is fixed
+                            Err(err) => {
+                                eprintln!("{} {:#}", "ERROR:".red().bold(), err);
+                                continue;
+                            },
+                        }
+                    }
+
+                    if analyzer.refresh(&changed) {
+                        break;
+                    }
+                },
+                Err(errors) => {
+                    for err in errors {
+                        eprintln!("{} {}", "WARNING:".yellow().bold(), err);
+                    }
+                },
+            }
+        }
+    }
+}
+
+// Watches exactly `dirs`, each without its subdirectories, adding and
+// removing watches as directories enter and leave the scan
+fn update_watches(
+    debouncer: &mut Debouncer<RecommendedWatcher, RecommendedCache>,
+    watched: &mut BTreeSet<PathBuf>,
+    dirs: BTreeSet<PathBuf>
+) {
+    for dir in watched.difference(&dirs) {
+        let _ = debouncer.unwatch(dir);
+    }
+
+    for dir in dirs.difference(watched) {
+        if let Err(err) = debouncer.watch(dir, RecursiveMode::NonRecursive) {
+            eprintln!("{} Failed to watch '{}': {}", "WARNING:".yellow().bold(), dir.display(), err);
+        }
+    }
+
+    *watched = dirs;
+}
+
+// The settings file in effect: the one given with --config, or the nearest
+// .ngstructure.toml above the analyzed path
+fn config_file(cli: &Cli, path: &Path, config: &Config) -> Option<PathBuf> {
+    if cli.no_config {
+        return None;
+    }
+
+    match cli.config {
+        Some(ref config_path) => Some(config_path.clone()),
+        None if config.dir.as_os_str().is_empty() => Some(path.join(CONFIG_FILE)),
+        None => Some(config.dir.join(CONFIG_FILE)),
+    }
+}
+
+// Whether the settings file in effect changed, or one was added between it
+// and the analyzed path, which would take its place
+fn config_changed(cli: &Cli, path: &Path, config: &Config, changed: &[PathBuf]) -> bool {
+    let Some(config_file) = config_file(cli, path, config) else {
+        return false;
+    };
+    let Some(config_dir) = absolute_path(&config_file).parent().and_then(|dir| dir.canonicalize().ok()) else {
+        return false;
+    };
+    let root = path.canonicalize().unwrap_or_else(|_| absolute_path(path));
+
+    // Compared by directory, as the file itself may just have been removed
+    changed.iter().any(|changed| {
+        let Some(dir) = changed.parent().and_then(|dir| dir.canonicalize().ok()) else {
+            return false;
+        };
+        if cli.config.is_some() {
+            dir == config_dir && changed.file_name() == config_file.file_name()
+        } else {
+            changed.file_name().is_some_and(|name| name == CONFIG_FILE)
+                && root.starts_with(&dir)
+                && dir.starts_with(&config_dir)
+        }
+    })
+}
+
+fn reload_config(cli: &Cli, path: &Path) -> Result<(Config, Analyzer)> {
+    let config = load_config(cli, path)?.unwrap_or_default();
+    let analyzer = build_analyzer(cli, path, &config)?;
+    Ok((config, analyzer))
+}
+
+fn absolute_path(path: impl AsRef<Path>) -> PathBuf {
+    std::path::absolute(path.as_ref()).unwrap_or_else(|_| path.as_ref().to_path_buf())
+}
+
+const DEFAULT_COMMAND: CommandDefaults = CommandDefaults { detailed: None, graph: None, audit: None };
+
+// The config given with --config, or the nearest one above the analyzed path
+fn load_config(cli: &Cli, path: &Path) -> Result<Option<Config>> {
+    if cli.no_config {
+        return Ok(None);
+    }
+
+    match cli.config {
+        Some(ref config_path) => Config::load(config_path).map(Some),
+        None => Config::discover(path),
+    }
 }
 
 fn is_angular_project(path: &Path) -> bool {
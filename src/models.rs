@@ -1,5 +1,7 @@
 // src/models.rs
-use serde::Serialize;
+use serde::{ Deserialize, Serialize };
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::PathBuf;
 
 #[derive(Debug, Serialize)]
@@ -23,7 +25,9 @@ pub struct FileNode {
 }
 
 // Hash トレイトを追加
-#[derive(Debug, Serialize, PartialEq, Eq, Clone, Hash, PartialOrd, Ord)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Hash, PartialOrd, Ord)]
+// Configured file types are written `component`, `ng-rx-action`, ...
+#[serde(rename_all(deserialize = "kebab-case"))]
 pub enum FileType {
     Component,
     Service,
@@ -45,14 +49,83 @@ pub enum FileType {
     Other,
 }
 
+// The workspace project a file belongs to, and its Nx tags. Serialized as
+// `project` and `tags` fields of the item that includes it.
+#[derive(Debug, Serialize, Clone, Default, PartialEq, Eq)]
+pub struct ProjectInfo {
+    pub project: Option<String>,
+    pub tags: Vec<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct Component {
     pub name: String,
     pub selector: Option<String>,
     pub path: PathBuf,
+    pub template_kind: Option<TemplateKind>,
     pub template_path: Option<PathBuf>,
+    #[serde(skip_serializing)]
+    pub inline_template: Option<String>,
     pub style_paths: Vec<PathBuf>,
+    pub inline_styles: usize,
     pub test_path: Option<PathBuf>,
+    pub standalone: bool,
+    pub imports: Vec<String>,
+    pub providers: Vec<String>,
+    pub host_directives: Vec<String>,
+    pub schemas: Vec<String>,
+    pub api: Vec<ApiMember>,
+    #[serde(flatten)]
+    pub project_info: ProjectInfo,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiMember {
+    pub name: String,
+    pub kind: ApiKind,
+    pub alias: Option<String>,
+    pub required: bool,
+    pub type_name: Option<String>,
+    pub signal: bool,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Copy)]
+pub enum ApiKind {
+    Input,
+    Output,
+    Model,
+    ViewChild,
+    ViewChildren,
+    ContentChild,
+    ContentChildren,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Copy)]
+pub enum TemplateKind {
+    External,
+    Inline,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Directive {
+    pub name: String,
+    pub selector: Option<String>,
+    pub path: PathBuf,
+    pub standalone: bool,
+    #[serde(flatten)]
+    pub project_info: ProjectInfo,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Pipe {
+    pub name: String,
+    // The name templates use, e.g. `currency`
+    pub pipe_name: Option<String>,
+    pub path: PathBuf,
+    pub standalone: bool,
+    pub pure: bool,
+    #[serde(flatten)]
+    pub project_info: ProjectInfo,
 }
 
 #[derive(Debug, Serialize)]
@@ -61,6 +134,8 @@ pub struct Service {
     pub path: PathBuf,
     pub injectable_scope: Option<String>,
     pub test_path: Option<PathBuf>,
+    #[serde(flatten)]
+    pub project_info: ProjectInfo,
 }
 
 #[derive(Debug, Serialize)]
@@ -72,13 +147,67 @@ pub struct Module {
     pub exports: Vec<String>,
     pub providers: Vec<String>,
     pub bootstrap: Vec<String>,
+    #[serde(flatten)]
+    pub project_info: ProjectInfo,
 }
 
+// One import, re-export, `import()` or `require()` statement
 #[derive(Debug, Serialize)]
 pub struct Dependency {
     pub source: PathBuf,
-    pub target: String,
+    pub specifier: String,
+    pub target: DependencyTarget,
     pub import_type: ImportType,
+    pub kind: DependencyKind,
+    // Empty for side-effect imports, `import()` and `require()`
+    pub names: Vec<ImportedName>,
+    pub type_only: bool,
+    pub dynamic: bool,
+    pub line: usize,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Copy)]
+pub enum DependencyKind {
+    Import,
+    ReExport,
+    Require,
+}
+
+// A symbol taken from the imported module. Default imports are named
+// `default` and namespace imports `*`, with the local name as the alias.
+#[derive(Debug, Serialize, Clone)]
+pub struct ImportedName {
+    pub name: String,
+    pub alias: Option<String>,
+    pub type_only: bool,
+}
+
+// Where an import points: a file in the project (relative to its root), an
+// external package, or a relative path that does not exist
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Hash, PartialOrd, Ord)]
+pub enum DependencyTarget {
+    File(PathBuf),
+    Package(String),
+    Unresolved(String),
+}
+
+impl fmt::Display for DependencyTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DependencyTarget::File(path) => write!(f, "{}", path.display()),
+            DependencyTarget::Package(name) => write!(f, "{}", name),
+            DependencyTarget::Unresolved(specifier) => write!(f, "{} (unresolved)", specifier),
+        }
+    }
+}
+
+// A group of files that import each other, directly or through other files
+#[derive(Debug, Serialize)]
+pub struct Cycle {
+    // One import chain through the group; the last file imports the first
+    pub chain: Vec<PathBuf>,
+    // Every file in the strongly connected component, sorted
+    pub files: Vec<PathBuf>,
 }
 
 // Hash トレイトを追加
@@ -95,10 +224,204 @@ pub enum ImportType {
     Other,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone)]
 pub struct Route {
     pub path: String,
+    // The URL from the application root, e.g. `/admin/users/:id`
+    pub full_path: String,
     pub component: Option<String>,
+    // Declared children followed by the routes `loadChildren` brings in
     pub children: Vec<Route>,
+    // `loadChildren` and `loadComponent` targets as `specifier#Export`
     pub lazy_module: Option<String>,
+    pub lazy_component: Option<String>,
+    // The file `loadChildren` resolves to
+    pub lazy_file: Option<PathBuf>,
+    // File declaring the route, relative to the project root
+    pub file: PathBuf,
+    pub line: usize,
+    pub can_activate: Vec<String>,
+    pub can_activate_child: Vec<String>,
+    pub can_deactivate: Vec<String>,
+    pub can_match: Vec<String>,
+    // Resolver per `resolve` key
+    pub resolve: BTreeMap<String, String>,
+    pub data: Option<serde_json::Value>,
+    pub title: Option<String>,
+    pub redirect_to: Option<String>,
+    pub path_match: Option<String>,
+    pub outlet: Option<String>,
+    pub providers: Vec<String>,
+    #[serde(flatten)]
+    pub project_info: ProjectInfo,
+}
+
+// An import from one workspace project into another that the Nx module
+// boundary rules forbid
+#[derive(Debug, Serialize)]
+pub struct BoundaryViolation {
+    pub source: PathBuf,
+    pub line: usize,
+    pub specifier: String,
+    pub target: PathBuf,
+    pub source_project: String,
+    pub target_project: String,
+    pub reason: String,
+}
+
+// An import between layers that a layer rule in `.ngstructure.toml` forbids
+#[derive(Debug, Serialize)]
+pub struct ArchitectureViolation {
+    pub rule: String,
+    pub source: PathBuf,
+    pub line: usize,
+    pub target: PathBuf,
+    pub source_layer: String,
+    pub target_layer: String,
+}
+
+// A problem found by auditing the route tree
+#[derive(Debug, Serialize)]
+pub struct RouteFinding {
+    pub kind: RouteFindingKind,
+    pub message: String,
+    // Full path of the route the finding is about
+    pub route: String,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum RouteFindingKind {
+    DuplicatePath,
+    BrokenRedirect,
+    Unreachable,
+    UnknownComponent,
+    UnresolvedLazyTarget,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RenderTree {
+    pub roots: Vec<RenderNode>,
+    pub edges: Vec<RenderEdge>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RenderNode {
+    pub name: String,
+    pub kind: RenderKind,
+    pub children: Vec<RenderNode>,
+    pub recursive: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RenderEdge {
+    pub parent: String,
+    pub child: String,
+    pub kind: RenderKind,
+    pub selector: String,
+    pub template: PathBuf,
+    pub line: usize,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum RenderKind {
+    Component,
+    Directive,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InjectionGraph {
+    pub edges: Vec<InjectionEdge>,
+    pub unresolved: Vec<String>,
+    pub injection_tokens: Vec<InjectionTokenDecl>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InjectionEdge {
+    pub consumer: String,
+    pub consumer_kind: ConsumerKind,
+    pub consumer_path: PathBuf,
+    pub token: String,
+    pub site: InjectionSite,
+    pub optional: bool,
+    pub provider: TokenSource,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum ConsumerKind {
+    Component,
+    Directive,
+    Pipe,
+    Service,
+    Module,
+    Guard,
+    Resolver,
+    Interceptor,
+    Function,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Copy)]
+pub enum InjectionSite {
+    Constructor,
+    InjectFunction,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Hash)]
+pub enum TokenSource {
+    Project(PathBuf),
+    Package(String),
+    Unresolved,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InjectionTokenDecl {
+    pub name: String,
+    pub path: PathBuf,
+    pub type_name: Option<String>,
+    pub description: Option<String>,
+    pub consumers: Vec<String>,
+}
+
+// Everything one `analyze_all` run finds, with the links between classes
+#[derive(Debug, Serialize)]
+pub struct ProjectModel {
+    pub structure: ProjectStructure,
+    pub components: Vec<Component>,
+    pub directives: Vec<Directive>,
+    pub pipes: Vec<Pipe>,
+    pub services: Vec<Service>,
+    pub modules: Vec<Module>,
+    pub routes: Vec<Route>,
+    pub dependencies: Vec<Dependency>,
+    pub links: Vec<ClassLinks>,
+}
+
+// Where a component, directive, pipe, service or module is declared and used
+#[derive(Debug, Serialize)]
+pub struct ClassLinks {
+    pub name: String,
+    pub kind: ClassKind,
+    // Relative to the project root
+    pub path: PathBuf,
+    // NgModules listing the class in `declarations`
+    pub declared_in: Vec<String>,
+    // Components and NgModules listing it in `imports`
+    pub imported_in: Vec<String>,
+    pub exported_from: Vec<String>,
+    // Components and NgModules listing it in `providers`
+    pub provided_in: Vec<String>,
+    // Full paths of the routes that render it
+    pub routes: Vec<String>,
+    // Files with an import of the class's file
+    pub imported_by: Vec<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum ClassKind {
+    Component,
+    Directive,
+    Pipe,
+    Service,
+    Module,
 }
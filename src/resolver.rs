@@ -0,0 +1,144 @@
+// src/resolver.rs
+use std::path::{Path, PathBuf};
+
+use crate::models::DependencyTarget;
+use crate::tsconfig::TsConfig;
+use crate::utils::fs_utils;
+
+// Extensions and index files tried, in order, when an import omits them
+const CANDIDATE_SUFFIXES: &[&str] = &[
+    "",
+    ".ts",
+    ".tsx",
+    ".d.ts",
+    ".mjs",
+    ".js",
+    "/index.ts",
+    "/index.tsx",
+    "/index.d.ts",
+    "/index.js",
+];
+
+pub struct ModuleResolver {
+    root: PathBuf,
+    tsconfig: Option<TsConfig>,
+}
+
+impl ModuleResolver {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: fs_utils::normalize_path(root),
+            tsconfig: TsConfig::load(root),
+        }
+    }
+
+    // Resolves an import specifier written in `from_file` to a file relative
+    // to the project root, or to the package it names. Non-relative
+    // specifiers go through tsconfig `paths` and `baseUrl` first.
+    pub fn resolve(&self, from_file: &Path, specifier: &str) -> DependencyTarget {
+        if !is_relative(specifier) {
+            let aliased = self
+                .tsconfig
+                .iter()
+                .flat_map(|tsconfig| tsconfig.candidates(specifier))
+                .find_map(|candidate| self.find_file(&candidate));
+
+            return match aliased {
+                Some(path) => DependencyTarget::File(path),
+                None => DependencyTarget::Package(specifier.to_string()),
+            };
+        }
+
+        let base_dir = from_file.parent().unwrap_or(Path::new(""));
+        match self.find_file(&base_dir.join(specifier)) {
+            Some(path) => DependencyTarget::File(path),
+            None => DependencyTarget::Unresolved(specifier.to_string()),
+        }
+    }
+
+    // Project-relative form of a path inside the project
+    pub fn relative_path(&self, path: &Path) -> PathBuf {
+        let path = fs_utils::normalize_path(path);
+        match path.strip_prefix(&self.root) {
+            Ok(relative) => relative.to_path_buf(),
+            Err(_) => path,
+        }
+    }
+
+    fn find_file(&self, base: &Path) -> Option<PathBuf> {
+        let base = fs_utils::normalize_path(base);
+        let base = base.to_string_lossy();
+
+        CANDIDATE_SUFFIXES
+            .iter()
+            .map(|suffix| PathBuf::from(format!("{}{}", base, suffix)))
+            .find(|candidate| candidate.is_file())
+            .map(|found| self.relative_path(&found))
+    }
+}
+
+fn is_relative(specifier: &str) -> bool {
+    specifier == "." || specifier == ".." || specifier.starts_with("./") || specifier.starts_with("../")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn fixture(files: &[&str]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for file in files {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        dir
+    }
+
+    fn resolve(dir: &tempfile::TempDir, from: &str, specifier: &str) -> DependencyTarget {
+        ModuleResolver::new(dir.path()).resolve(&dir.path().join(from), specifier)
+    }
+
+    #[test]
+    fn extensions_are_tried_in_order() {
+        let dir = fixture(&["src/app.ts", "src/util.js", "src/types.d.ts", "src/both.ts", "src/both.js"]);
+
+        assert_eq!(resolve(&dir, "src/main.ts", "./app"), DependencyTarget::File(PathBuf::from("src/app.ts")));
+        assert_eq!(resolve(&dir, "src/main.ts", "./util"), DependencyTarget::File(PathBuf::from("src/util.js")));
+        assert_eq!(resolve(&dir, "src/main.ts", "./types"), DependencyTarget::File(PathBuf::from("src/types.d.ts")));
+        assert_eq!(resolve(&dir, "src/main.ts", "./both"), DependencyTarget::File(PathBuf::from("src/both.ts")));
+    }
+
+    #[test]
+    fn directories_resolve_to_their_index() {
+        let dir = fixture(&["src/core/index.ts", "src/shared/index.js", "src/app/app.ts"]);
+
+        assert_eq!(resolve(&dir, "src/app/app.ts", "../core"), DependencyTarget::File(PathBuf::from("src/core/index.ts")));
+        assert_eq!(resolve(&dir, "src/app/app.ts", "../shared"), DependencyTarget::File(PathBuf::from("src/shared/index.js")));
+        assert_eq!(resolve(&dir, "src/main.ts", "./core/"), DependencyTarget::File(PathBuf::from("src/core/index.ts")));
+    }
+
+    #[test]
+    fn a_file_wins_over_a_directory_of_the_same_name() {
+        let dir = fixture(&["src/core.ts", "src/core/index.ts"]);
+
+        assert_eq!(resolve(&dir, "src/main.ts", "./core"), DependencyTarget::File(PathBuf::from("src/core.ts")));
+    }
+
+    #[test]
+    fn missing_relative_files_are_unresolved() {
+        let dir = fixture(&["src/main.ts"]);
+
+        assert_eq!(resolve(&dir, "src/main.ts", "./missing"), DependencyTarget::Unresolved("./missing".to_string()));
+    }
+
+    #[test]
+    fn bare_specifiers_go_through_tsconfig_before_packages() {
+        let dir = fixture(&["libs/ui/src/index.ts"]);
+        fs::write(dir.path().join("tsconfig.json"), r#"{ "compilerOptions": { "paths": { "@org/ui": ["libs/ui/src"] } } }"#).unwrap();
+
+        assert_eq!(resolve(&dir, "src/main.ts", "@org/ui"), DependencyTarget::File(PathBuf::from("libs/ui/src/index.ts")));
+        assert_eq!(resolve(&dir, "src/main.ts", "@angular/core"), DependencyTarget::Package("@angular/core".to_string()));
+    }
+}
@@ -0,0 +1,303 @@
+// src/route_audit.rs
+//
+// Checks a stitched route tree for routes that can never match, redirects
+// and lazy imports that lead nowhere, and components that do not exist.
+
+use std::collections::{ BTreeMap, HashSet };
+use std::path::Path;
+
+use crate::models::{ Component, DependencyTarget, Route, RouteFinding, RouteFindingKind };
+use crate::resolver::ModuleResolver;
+
+pub fn audit_routes(
+    root: &Path,
+    routes: &[Route],
+    components: &[Component],
+    resolver: &ModuleResolver
+) -> Vec<RouteFinding> {
+    let component_names: HashSet<&str> = components.iter().map(|c| c.name.as_str()).collect();
+
+    let mut all_routes = Vec::new();
+    collect_routes(routes, &mut all_routes);
+
+    // The root routes of each file are one router configuration. Roots from
+    // different files, such as the apps of a workspace, never compete.
+    let mut roots: BTreeMap<&Path, Vec<&Route>> = BTreeMap::new();
+    for route in routes {
+        roots.entry(&route.file).or_default().push(route);
+    }
+
+    let mut findings = Vec::new();
+    for siblings in roots.values() {
+        check_siblings(siblings, &mut findings);
+    }
+    for route in &all_routes {
+        let children: Vec<&Route> = route.children.iter().collect();
+        check_siblings(&children, &mut findings);
+    }
+
+    for route in &all_routes {
+        if let Some(ref target) = route.redirect_to
+            && let Some(target_path) = redirect_target(route, target)
+        {
+            // Redirects stay within the application they are declared in. A
+            // wildcard matches any URL and a route cannot redirect to itself,
+            // so neither counts as a target.
+            let app_routes: Vec<&Route> = all_routes
+                .iter()
+                .copied()
+                .filter(|r| r.project_info.project == route.project_info.project)
+                .filter(|r| r.path != "**" && !std::ptr::eq(*r, *route))
+                .collect();
+
+            if !app_routes.iter().any(|r| path_matches(&r.full_path, &target_path))
+                && !under_unknown_lazy_route(&app_routes, &target_path)
+            {
+                findings.push(finding(
+                    route,
+                    RouteFindingKind::BrokenRedirect,
+                    format!("redirectTo '{}' does not match any route ({})", target, target_path),
+                ));
+            }
+        }
+
+        if let Some(ref component) = route.component
+            && !component_names.contains(component.as_str())
+        {
+            findings.push(finding(
+                route,
+                RouteFindingKind::UnknownComponent,
+                format!("component {} is not a component in this project", component),
+            ));
+        }
+
+        for (label, target) in [("loadChildren", &route.lazy_module), ("loadComponent", &route.lazy_component)] {
+            let Some(target) = target else { continue };
+            let (specifier, export) = match target.split_once('#') {
+                Some((specifier, export)) => (specifier, Some(export)),
+                None => (target.as_str(), None),
+            };
+
+            match resolver.resolve(&root.join(&route.file), specifier) {
+                DependencyTarget::File(_) | DependencyTarget::Package(_) => {}
+                DependencyTarget::Unresolved(_) => {
+                    findings.push(finding(
+                        route,
+                        RouteFindingKind::UnresolvedLazyTarget,
+                        format!("{} target '{}' does not resolve to a file", label, specifier),
+                    ));
+                    continue;
+                }
+            }
+
+            if label == "loadComponent"
+                && let Some(export) = export
+                && !component_names.contains(export)
+            {
+                findings.push(finding(
+                    route,
+                    RouteFindingKind::UnknownComponent,
+                    format!("loadComponent export {} is not a component in this project", export),
+                ));
+            }
+        }
+    }
+
+    findings.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    findings
+}
+
+fn collect_routes<'a>(routes: &'a [Route], out: &mut Vec<&'a Route>) {
+    for route in routes {
+        out.push(route);
+        collect_routes(&route.children, out);
+    }
+}
+
+// Duplicates and routes after a `**` wildcard, among routes the router tries
+// in order at one level
+fn check_siblings(siblings: &[&Route], findings: &mut Vec<RouteFinding>) {
+    for (i, route) in siblings.iter().enumerate() {
+        let earlier = &siblings[..i];
+
+        // Redirects and `canMatch` guards make repeated paths intentional
+        let is_alternative = |r: &Route| r.redirect_to.is_some() || !r.can_match.is_empty();
+
+        if let Some(wildcard) = earlier.iter().find(|r| r.path == "**" && r.outlet == route.outlet) {
+            findings.push(finding(
+                route,
+                RouteFindingKind::Unreachable,
+                format!("'{}' comes after the wildcard route at line {} and can never match", route.full_path, wildcard.line),
+            ));
+        } else if !is_alternative(route)
+            && let Some(first) = earlier.iter().find(|r| r.path == route.path && r.outlet == route.outlet && !is_alternative(r))
+        {
+            findings.push(finding(
+                route,
+                RouteFindingKind::DuplicatePath,
+                format!("'{}' is already defined at {}:{}", route.full_path, first.file.display(), first.line),
+            ));
+        }
+    }
+}
+
+// Absolute form of a string redirect. Function redirects are not followed.
+fn redirect_target(route: &Route, target: &str) -> Option<String> {
+    if target == "(inline)" || target.ends_with("(...)") {
+        return None;
+    }
+
+    if target.starts_with('/') {
+        return Some(target.to_string());
+    }
+
+    // Relative redirects replace the redirecting route's own segment
+    let parent = route
+        .full_path
+        .strip_suffix(route.path.trim_matches('/'))
+        .unwrap_or(&route.full_path)
+        .trim_end_matches('/');
+    let absolute = format!("{}/{}", parent, target);
+    let absolute = absolute.trim_end_matches('/');
+    Some(if absolute.is_empty() { "/".to_string() } else { absolute.to_string() })
+}
+
+// Whether a route's full path matches a URL, treating `:param` segments in
+// either as wildcards and `**` as matching the rest
+fn path_matches(route_path: &str, url: &str) -> bool {
+    let route_segments: Vec<&str> = route_path.split('/').filter(|s| !s.is_empty()).collect();
+    let url_segments: Vec<&str> = url.split('/').filter(|s| !s.is_empty()).collect();
+
+    for (i, segment) in route_segments.iter().enumerate() {
+        if *segment == "**" {
+            return true;
+        }
+        match url_segments.get(i) {
+            Some(url_segment) if segment.starts_with(':') || url_segment.starts_with(':') || segment == url_segment => {}
+            _ => return false,
+        }
+    }
+
+    route_segments.len() == url_segments.len()
+}
+
+// A redirect into a lazy route whose children could not be loaded cannot be
+// checked
+fn under_unknown_lazy_route(routes: &[&Route], url: &str) -> bool {
+    routes.iter().any(|r| {
+        let under = r.full_path == "/" || url == r.full_path || url.starts_with(&format!("{}/", r.full_path));
+        r.lazy_module.is_some() && r.children.is_empty() && under
+    })
+}
+
+fn finding(route: &Route, kind: RouteFindingKind, message: String) -> RouteFinding {
+    RouteFinding {
+        kind,
+        message,
+        route: route.full_path.clone(),
+        file: route.file.clone(),
+        line: route.line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProjectInfo;
+    use std::path::PathBuf;
+
+    fn route(path: &str, file: &str, project: &str) -> Route {
+        Route {
+            path: path.to_string(),
+            full_path: format!("/{}", path),
+            component: None,
+            children: Vec::new(),
+            lazy_module: None,
+            lazy_component: None,
+            lazy_file: None,
+            file: PathBuf::from(file),
+            line: 1,
+            can_activate: Vec::new(),
+            can_activate_child: Vec::new(),
+            can_deactivate: Vec::new(),
+            can_match: Vec::new(),
+            resolve: BTreeMap::new(),
+            data: None,
+            title: None,
+            redirect_to: None,
+            path_match: None,
+            outlet: None,
+            providers: Vec::new(),
+            project_info: ProjectInfo { project: Some(project.to_string()), tags: Vec::new() },
+        }
+    }
+
+    fn kinds(routes: &[Route]) -> Vec<(String, RouteFindingKind)> {
+        let dir = tempfile::tempdir().unwrap();
+        let resolver = ModuleResolver::new(dir.path());
+        audit_routes(dir.path(), routes, &[], &resolver)
+            .into_iter()
+            .map(|f| (f.route, f.kind))
+            .collect()
+    }
+
+    #[test]
+    fn apps_of_a_workspace_do_not_shadow_each_other() {
+        let a = "apps/a/src/app/app.routes.ts";
+        let b = "apps/b/src/app/app.routes.ts";
+        let routes = vec![
+            route("", a, "a"),
+            route("**", a, "a"),
+            route("", b, "b"),
+            route("**", b, "b"),
+        ];
+
+        assert!(kinds(&routes).is_empty());
+    }
+
+    #[test]
+    fn routes_after_a_wildcard_in_the_same_file_are_unreachable() {
+        let a = "apps/a/src/app/app.routes.ts";
+        let routes = vec![
+            route("**", a, "a"),
+            route("home", a, "a"),
+            route("home", "apps/b/src/app/app.routes.ts", "b"),
+        ];
+
+        let found = kinds(&routes);
+        assert!(found.contains(&("/home".to_string(), RouteFindingKind::Unreachable)));
+        assert_eq!(found.len(), 1);
+    }
+
+    #[test]
+    fn redirects_only_match_routes_of_their_own_app() {
+        let mut redirect = route("", "apps/a/src/app/app.routes.ts", "a");
+        redirect.redirect_to = Some("home".to_string());
+        let routes = vec![redirect, route("home", "apps/b/src/app/app.routes.ts", "b")];
+
+        assert_eq!(kinds(&routes), vec![("/".to_string(), RouteFindingKind::BrokenRedirect)]);
+    }
+
+    #[test]
+    fn wildcards_do_not_hide_broken_redirects() {
+        let a = "src/app/app.routes.ts";
+        let mut redirect = route("old", a, "a");
+        redirect.redirect_to = Some("nowhere".to_string());
+        let routes = vec![redirect, route("**", a, "a")];
+
+        assert_eq!(kinds(&routes), vec![("/old".to_string(), RouteFindingKind::BrokenRedirect)]);
+    }
+
+    #[test]
+    fn a_wildcard_redirect_is_checked_against_the_other_routes() {
+        let a = "src/app/app.routes.ts";
+        let mut wildcard = route("**", a, "a");
+        wildcard.redirect_to = Some("nowhere".to_string());
+        let mut fallback = route("**", a, "a");
+        fallback.redirect_to = Some("home".to_string());
+        let routes = vec![route("home", a, "a"), wildcard];
+
+        assert_eq!(kinds(&routes), vec![("/**".to_string(), RouteFindingKind::BrokenRedirect)]);
+        assert!(kinds(&[route("home", a, "a"), fallback]).is_empty());
+    }
+}
@@ -0,0 +1,236 @@
+// src/scanner.rs
+//
+// The single walk of the project tree that every analysis reads from. Ignore
+// files, excluded directories, include/exclude globs, test and style files,
+// the depth limit and symlinks are handled here, so all subcommands see the
+// same files.
+
+use ignore::WalkBuilder;
+use std::collections::BTreeMap;
+use std::path::{ Path, PathBuf };
+
+use crate::config::{ self, FileFilter };
+use crate::models::FileType;
+
+// Directories that are never analyzed
+const EXCLUDED_DIRS: &[&str] = &[".angular", ".vscode", ".git"];
+
+#[derive(Debug, Default, Clone)]
+pub struct ScanOptions {
+    pub include_tests: bool,
+    pub include_styles: bool,
+    pub include_node_modules: bool,
+    // Directory levels below the root to descend; unlimited when None
+    pub max_depth: Option<usize>,
+    pub filter: FileFilter,
+    // File name suffixes from the config, checked before the built-in ones
+    pub file_types: BTreeMap<String, FileType>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexedFile {
+    pub path: PathBuf,
+    // Relative to the analyzed root
+    pub relative_path: PathBuf,
+    pub file_type: FileType,
+}
+
+// Every file of the project that passed the scan options, sorted by path
+#[derive(Debug, Default)]
+pub struct FileIndex {
+    pub files: Vec<IndexedFile>,
+    // Directories the walk entered, the root included, sorted
+    pub dirs: Vec<PathBuf>,
+    // Nx `project.json` files in the scanned directories, kept even when the
+    // include/exclude globs leave them out of `files`
+    pub project_json_files: Vec<PathBuf>,
+}
+
+impl FileIndex {
+    // Whether `path` is an indexed file or a directory the walk entered
+    pub fn contains(&self, path: &Path) -> bool {
+        self.files.binary_search_by(|file| file.path.as_path().cmp(path)).is_ok()
+            || self.dirs.binary_search_by(|dir| dir.as_path().cmp(path)).is_ok()
+    }
+}
+
+impl ScanOptions {
+    pub fn file_type(&self, path: &Path) -> FileType {
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+
+        self.file_types
+            .iter()
+            .filter(|(suffix, _)| file_name.ends_with(suffix.as_str()))
+            .max_by_key(|(suffix, _)| suffix.len())
+            .map(|(_, file_type)| file_type.clone())
+            .unwrap_or_else(|| determine_file_type(path))
+    }
+
+    fn is_excluded_dir(&self, relative_path: &Path) -> bool {
+        let name = relative_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if EXCLUDED_DIRS.contains(&name) || (!self.include_node_modules && name == "node_modules") {
+            return true;
+        }
+
+        self.filter.is_excluded(relative_path)
+    }
+
+    fn includes_file(&self, relative_path: &Path) -> bool {
+        let file_name = relative_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+
+        if !self.include_tests && file_name.contains(".spec.") {
+            return false;
+        }
+
+        if !self.include_styles && is_style_file(relative_path) {
+            return false;
+        }
+
+        self.filter.includes_file(relative_path)
+    }
+}
+
+// Walks the tree once, honoring .gitignore, .ignore and .ngstructureignore
+// files. Unreadable entries and symlink loops are skipped.
+pub fn scan(root: &Path, options: &ScanOptions) -> FileIndex {
+    let dir_options = options.clone();
+    let dir_root = root.to_path_buf();
+
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .standard_filters(false)
+        .git_ignore(true)
+        .git_exclude(true)
+        .ignore(true)
+        .parents(true)
+        .require_git(false)
+        .follow_links(true)
+        .max_depth(options.max_depth)
+        .filter_entry(move |e| {
+            let relative = e.path().strip_prefix(&dir_root).unwrap_or(e.path());
+            e.depth() == 0 || !e.file_type().is_some_and(|t| t.is_dir()) || !dir_options.is_excluded_dir(relative)
+        });
+    builder.add_custom_ignore_filename(config::IGNORE_FILE);
+
+    let mut index = FileIndex::default();
+    for entry in builder.build().filter_map(|e| e.ok()) {
+        if entry.file_type().is_some_and(|t| t.is_dir()) {
+            index.dirs.push(entry.into_path());
+            continue;
+        }
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+
+        let path = entry.into_path();
+        if path.file_name().is_some_and(|name| name == "project.json") {
+            index.project_json_files.push(path.clone());
+        }
+
+        let relative_path = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+        if options.includes_file(&relative_path) {
+            index.files.push(IndexedFile {
+                file_type: options.file_type(&path),
+                relative_path,
+                path,
+            });
+        }
+    }
+
+    index.files.sort_by(|a, b| a.path.cmp(&b.path));
+    index.dirs.sort();
+    index.project_json_files.sort();
+    index
+}
+
+fn determine_file_type(path: &Path) -> FileType {
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("");
+
+    if file_name.ends_with(".action.ts") {
+        FileType::NgRxAction
+    } else if file_name.ends_with(".html") {
+        FileType::Template
+    } else if file_name.ends_with(".reducer.ts") {
+        FileType::NgRxReducer
+    } else if file_name.ends_with(".effects.ts") {
+        FileType::NgRxEffect
+    } else if file_name.ends_with(".selector.ts") {
+        FileType::NgRxSelector
+    } else if file_name.ends_with(".ngrx.ts") {
+        FileType::NgRxOther
+    } else if file_name.ends_with(".component.ts") {
+        FileType::Component
+    } else if file_name.ends_with(".service.ts") {
+        FileType::Service
+    } else if file_name.ends_with(".module.ts") {
+        FileType::Module
+    } else if file_name.ends_with(".directive.ts") {
+        FileType::Directive
+    } else if file_name.ends_with(".pipe.ts") {
+        FileType::Pipe
+    } else if file_name.ends_with(".guard.ts") {
+        FileType::Guard
+    } else if file_name.ends_with(".resolver.ts") {
+        FileType::Resolver
+    } else if file_name.ends_with(".model.ts") || file_name.ends_with(".interface.ts") {
+        FileType::Model
+    } else if file_name == "tsconfig.json" || file_name == "angular.json" {
+        FileType::Config
+    } else if file_name.ends_with(".spec.ts") {
+        FileType::Test
+    } else if is_style_file(path) {
+        FileType::Style
+    } else {
+        FileType::Other
+    }
+}
+
+fn is_style_file(path: &Path) -> bool {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+
+    matches!(extension, "css" | "scss" | "sass" | "less")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn project_json_files_follow_the_scan_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for project in ["apps/a", "libs/b", "node_modules/c", "dist/d"] {
+            fs::create_dir_all(root.join(project)).unwrap();
+            fs::write(root.join(project).join("project.json"), "{}").unwrap();
+        }
+        fs::write(root.join(".gitignore"), "dist\n").unwrap();
+
+        let index = scan(root, &ScanOptions::default());
+        assert_eq!(index.project_json_files, vec![
+            root.join("apps/a/project.json"),
+            root.join("libs/b/project.json"),
+        ]);
+    }
+
+    #[test]
+    fn only_entered_directories_are_indexed() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for sub in ["src/app", "node_modules/c", ".git/objects", "dist/d"] {
+            fs::create_dir_all(root.join(sub)).unwrap();
+        }
+        fs::write(root.join(".gitignore"), "dist\n").unwrap();
+
+        let index = scan(root, &ScanOptions::default());
+        assert_eq!(index.dirs, vec![root.to_path_buf(), root.join("src"), root.join("src/app")]);
+        assert!(index.contains(&root.join("src/app")));
+        assert!(!index.contains(&root.join("dist")));
+    }
+}
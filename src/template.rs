@@ -0,0 +1,378 @@
+// src/template.rs
+//
+// Just enough of an Angular template parser to find out which elements a
+// template renders and which component or directive selectors they match.
+
+#[derive(Debug)]
+pub struct Element {
+    pub tag: String,
+    // Attribute names that take part in selector matching, with their static values
+    pub attributes: Vec<(String, Option<String>)>,
+    pub classes: Vec<String>,
+    pub line: usize,
+}
+
+pub fn parse_elements(html: &str) -> Vec<Element> {
+    let chars: Vec<char> = html.chars().collect();
+    let mut elements = Vec::new();
+    let mut pos = 0;
+    let mut line = 1;
+
+    while pos < chars.len() {
+        let c = chars[pos];
+
+        if c == '\n' {
+            line += 1;
+            pos += 1;
+        } else if starts_with(&chars, pos, "<!--") {
+            let end = find(&chars, pos + 4, "-->").map(|i| i + 3).unwrap_or(chars.len());
+            line += count_newlines(&chars[pos..end]);
+            pos = end;
+        } else if c == '<' && chars.get(pos + 1).is_some_and(|n| n.is_ascii_alphabetic()) {
+            let start_line = line;
+            let (element, end) = parse_start_tag(&chars, pos + 1, start_line);
+            line += count_newlines(&chars[pos..end]);
+            pos = end;
+
+            // Raw text elements can contain anything, including `<`
+            if matches!(element.tag.as_str(), "script" | "style") {
+                let close = format!("</{}", element.tag);
+                let end = find(&chars, pos, &close).unwrap_or(chars.len());
+                line += count_newlines(&chars[pos..end]);
+                pos = end;
+            }
+
+            elements.push(element);
+        } else {
+            pos += 1;
+        }
+    }
+
+    elements
+}
+
+fn parse_start_tag(chars: &[char], mut pos: usize, line: usize) -> (Element, usize) {
+    let tag = read_while(chars, &mut pos, |c| c.is_alphanumeric() || matches!(c, '-' | '_' | ':'));
+
+    let mut element = Element {
+        tag: tag.to_lowercase(),
+        attributes: Vec::new(),
+        classes: Vec::new(),
+        line,
+    };
+
+    loop {
+        read_while(chars, &mut pos, char::is_whitespace);
+
+        match chars.get(pos) {
+            None => break,
+            Some('>') => {
+                pos += 1;
+                break;
+            }
+            Some('/') => {
+                pos += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        let name = read_while(chars, &mut pos, |c| !c.is_whitespace() && !matches!(c, '=' | '>' | '/'));
+        if name.is_empty() {
+            pos += 1;
+            continue;
+        }
+
+        read_while(chars, &mut pos, char::is_whitespace);
+        let value = if chars.get(pos) == Some(&'=') {
+            pos += 1;
+            read_while(chars, &mut pos, char::is_whitespace);
+            Some(read_attribute_value(chars, &mut pos))
+        } else {
+            None
+        };
+
+        if name == "class" {
+            if let Some(ref value) = value {
+                element.classes.extend(value.split_whitespace().map(str::to_string));
+            }
+        } else if let Some(binding) = binding_name(&name) {
+            element.attributes.push((binding, value));
+        }
+    }
+
+    (element, pos)
+}
+
+fn read_attribute_value(chars: &[char], pos: &mut usize) -> String {
+    match chars.get(*pos) {
+        Some(&quote) if quote == '"' || quote == '\'' => {
+            *pos += 1;
+            let value = read_while(chars, pos, |c| c != quote);
+            // An unterminated value runs to the end of the template
+            if chars.get(*pos) == Some(&quote) {
+                *pos += 1;
+            }
+            value
+        }
+        _ => read_while(chars, pos, |c| !c.is_whitespace() && c != '>'),
+    }
+}
+
+// Maps an attribute as written to the name Angular matches selectors against.
+// `[foo]`, `[(foo)]`, `bind-foo` and `*foo` all match `[foo]`; event bindings,
+// references and attribute/class/style bindings never match.
+fn binding_name(name: &str) -> Option<String> {
+    let name = if let Some(inner) = name.strip_prefix("[(").and_then(|n| n.strip_suffix(")]")) {
+        inner
+    } else if let Some(inner) = name.strip_prefix('[').and_then(|n| n.strip_suffix(']')) {
+        if inner.starts_with("attr.") || inner.starts_with("class.") || inner.starts_with("style.") || inner.starts_with('@') {
+            return None;
+        }
+        inner
+    } else if let Some(inner) = name.strip_prefix("bind-") {
+        inner
+    } else if let Some(inner) = name.strip_prefix("bindon-") {
+        inner
+    } else if let Some(inner) = name.strip_prefix('*') {
+        inner
+    } else if name.starts_with('(') || name.starts_with('#') || name.starts_with('@') || name.starts_with("on-") || name.starts_with("let-") || name.starts_with("ref-") {
+        return None;
+    } else {
+        name
+    };
+
+    Some(name.to_string())
+}
+
+fn read_while(chars: &[char], pos: &mut usize, pred: impl Fn(char) -> bool) -> String {
+    let start = *pos;
+    while chars.get(*pos).is_some_and(|&c| pred(c)) {
+        *pos += 1;
+    }
+    chars[start..*pos].iter().collect()
+}
+
+fn starts_with(chars: &[char], pos: usize, pattern: &str) -> bool {
+    pattern.chars().enumerate().all(|(i, p)| chars.get(pos + i) == Some(&p))
+}
+
+fn find(chars: &[char], from: usize, pattern: &str) -> Option<usize> {
+    (from..chars.len()).find(|&i| starts_with(chars, i, pattern))
+}
+
+fn count_newlines(chars: &[char]) -> usize {
+    chars.iter().filter(|&&c| c == '\n').count()
+}
+
+// A parsed component or directive selector such as `app-card`,
+// `button[appHighlight]`, `.panel` or `[ngModel]:not([formControl])`.
+#[derive(Debug)]
+pub struct Selector {
+    alternatives: Vec<SimpleSelector>,
+}
+
+#[derive(Debug, Default)]
+struct SimpleSelector {
+    tag: Option<String>,
+    attributes: Vec<(String, Option<String>)>,
+    classes: Vec<String>,
+    not: Vec<SimpleSelector>,
+}
+
+impl Selector {
+    pub fn parse(selector: &str) -> Self {
+        let alternatives = split_top_level(selector)
+            .into_iter()
+            .map(|part| SimpleSelector::parse(part.trim()))
+            .filter(|s| s.tag.is_some() || !s.attributes.is_empty() || !s.classes.is_empty())
+            .collect();
+
+        Self { alternatives }
+    }
+
+    pub fn matches(&self, element: &Element) -> bool {
+        self.alternatives.iter().any(|s| s.matches(element))
+    }
+}
+
+impl SimpleSelector {
+    fn parse(text: &str) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        let mut selector = SimpleSelector::default();
+        let mut pos = 0;
+
+        let tag = read_while(&chars, &mut pos, |c| c.is_alphanumeric() || matches!(c, '-' | '_'));
+        if !tag.is_empty() {
+            selector.tag = Some(tag.to_lowercase());
+        }
+
+        while pos < chars.len() {
+            match chars[pos] {
+                '[' => {
+                    pos += 1;
+                    let inner = read_while(&chars, &mut pos, |c| c != ']');
+                    if pos < chars.len() {
+                        pos += 1;
+                    }
+                    let attribute = match inner.split_once('=') {
+                        Some((name, value)) => {
+                            let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+                            (name.trim().to_string(), Some(value.to_string()))
+                        }
+                        None => (inner.trim().to_string(), None),
+                    };
+                    selector.attributes.push(attribute);
+                }
+                '.' => {
+                    pos += 1;
+                    let class = read_while(&chars, &mut pos, |c| c.is_alphanumeric() || matches!(c, '-' | '_'));
+                    selector.classes.push(class);
+                }
+                ':' if starts_with(&chars, pos, ":not(") => {
+                    pos += 5;
+                    let mut depth = 1;
+                    let start = pos;
+                    while pos < chars.len() {
+                        match chars[pos] {
+                            '(' => depth += 1,
+                            ')' => {
+                                depth -= 1;
+                                if depth == 0 {
+                                    break;
+                                }
+                            }
+                            _ => {}
+                        }
+                        pos += 1;
+                    }
+                    let inner: String = chars[start..pos.min(chars.len())].iter().collect();
+                    selector.not.push(SimpleSelector::parse(inner.trim()));
+                    pos += 1;
+                }
+                _ => pos += 1,
+            }
+        }
+
+        selector
+    }
+
+    fn matches(&self, element: &Element) -> bool {
+        if let Some(ref tag) = self.tag
+            && *tag != element.tag
+        {
+            return false;
+        }
+
+        let attributes_match = self.attributes.iter().all(|(name, value)| {
+            element.attributes.iter().any(|(el_name, el_value)| {
+                el_name.eq_ignore_ascii_case(name) && (value.is_none() || value == el_value)
+            })
+        });
+        let classes_match = self.classes.iter().all(|class| element.classes.contains(class));
+
+        attributes_match && classes_match && !self.not.iter().any(|n| n.matches(element))
+    }
+}
+
+fn split_top_level(selector: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, c) in selector.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&selector[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&selector[start..]);
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(html: &str) -> Vec<String> {
+        parse_elements(html).into_iter().map(|e| e.tag).collect()
+    }
+
+    #[test]
+    fn elements_attributes_and_classes() {
+        let elements = parse_elements(
+            "<app-card class=\"a  b\" [title]=\"t\" (click)=\"go()\" *ngIf=\"x\" type=button>\n<!-- <app-hidden> -->\n<input/></app-card>",
+        );
+
+        assert_eq!(elements.len(), 2);
+        assert_eq!(elements[0].tag, "app-card");
+        assert_eq!(elements[0].classes, ["a", "b"]);
+        let names: Vec<&str> = elements[0].attributes.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["title", "ngIf", "type"]);
+        assert_eq!(elements[0].attributes[2].1.as_deref(), Some("button"));
+        assert_eq!((elements[1].tag.as_str(), elements[1].line), ("input", 3));
+    }
+
+    #[test]
+    fn raw_text_elements_are_skipped() {
+        assert_eq!(tags("<style>a < b { }</style><script>if (a <b) {}</script><p></p>"), ["style", "script", "p"]);
+    }
+
+    #[test]
+    fn malformed_templates_do_not_panic() {
+        let templates = [
+            "<div class=\"foo",
+            "<div class='foo",
+            "<div class=",
+            "<div [a]=\"b\" c='",
+            "<div",
+            "<",
+            "<!-- open",
+            "<script>open",
+            "<a b=\"1\"><p class=\"x></p>",
+        ];
+        for template in templates {
+            parse_elements(template);
+        }
+    }
+
+    #[test]
+    fn every_prefix_of_a_template_parses() {
+        let html = "<app-list class=\"x y\" [items]='items' #list>\n  <app-item *ngFor=\"let i of items\" (select)=\"pick(i)\"></app-item>\n</app-list>";
+        for (end, _) in html.char_indices() {
+            parse_elements(&html[..end]);
+        }
+    }
+
+    #[test]
+    fn unterminated_values_run_to_the_end() {
+        let elements = parse_elements("<div class=\"foo bar");
+
+        assert_eq!(elements.len(), 1);
+        assert_eq!(elements[0].classes, ["foo", "bar"]);
+    }
+
+    #[test]
+    fn selectors_match_tags_attributes_classes_and_not() {
+        let elements = parse_elements("<button appHighlight class=\"panel\" [ngModel]=\"m\"></button><input [formControl]=\"c\" ngModel>");
+
+        assert!(Selector::parse("button[appHighlight]").matches(&elements[0]));
+        assert!(Selector::parse(".panel, app-x").matches(&elements[0]));
+        assert!(Selector::parse("[ngModel]:not([formControl])").matches(&elements[0]));
+        assert!(!Selector::parse("[ngModel]:not([formControl])").matches(&elements[1]));
+        assert!(!Selector::parse("app-card").matches(&elements[0]));
+    }
+
+    #[test]
+    fn malformed_selectors_do_not_panic() {
+        for selector in ["[a", "a[b=", ":not(", ":not([a]", ".", "a,", ")("] {
+            Selector::parse(selector);
+        }
+    }
+}
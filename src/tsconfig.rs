@@ -0,0 +1,233 @@
+// src/tsconfig.rs
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::utils::{fs_utils, json_utils};
+
+// Guards against `extends` cycles
+const MAX_EXTENDS_DEPTH: usize = 16;
+
+// The module resolution settings of a tsconfig.json, merged across its
+// `extends` chain
+#[derive(Debug, Default)]
+pub struct TsConfig {
+    pub base_url: Option<PathBuf>,
+    // `compilerOptions.paths` patterns in declaration order
+    pub paths: Vec<(String, Vec<String>)>,
+    // Directory that `paths` targets are relative to
+    pub paths_base: PathBuf,
+}
+
+impl TsConfig {
+    // Loads `tsconfig.json` (or Nx's `tsconfig.base.json`) from the project root
+    pub fn load(root: &Path) -> Option<TsConfig> {
+        ["tsconfig.json", "tsconfig.base.json"]
+            .iter()
+            .map(|name| root.join(name))
+            .find(|path| path.is_file())
+            .map(|path| {
+                let mut config = TsConfig::default();
+                config.apply_file(root, &path, 0);
+                config
+            })
+    }
+
+    // Candidate file paths (without extension) for a non-relative specifier,
+    // in the order TypeScript tries them
+    pub fn candidates(&self, specifier: &str) -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+
+        // The pattern with the longest prefix before `*` wins
+        let best = self
+            .paths
+            .iter()
+            .filter_map(|(pattern, targets)| match_pattern(pattern, specifier).map(|m| (pattern, targets, m)))
+            .max_by_key(|(pattern, _, _)| pattern.find('*').unwrap_or(pattern.len()));
+
+        if let Some((_, targets, matched)) = best {
+            for target in targets {
+                let target = target.replacen('*', matched, 1);
+                candidates.push(fs_utils::normalize_path(&self.paths_base.join(target)));
+            }
+        }
+
+        if let Some(ref base_url) = self.base_url {
+            candidates.push(fs_utils::normalize_path(&base_url.join(specifier)));
+        }
+
+        candidates
+    }
+
+    // Applies a config file after the files it extends, so that its own
+    // options take precedence
+    fn apply_file(&mut self, root: &Path, path: &Path, depth: usize) {
+        if depth > MAX_EXTENDS_DEPTH {
+            return;
+        }
+
+        let Some(config) = fs::read_to_string(path).ok().and_then(|c| json_utils::parse_jsonc(&c).ok()) else {
+            return;
+        };
+        let config_dir = path.parent().unwrap_or(Path::new(""));
+
+        let extends = match &config["extends"] {
+            Value::String(parent) => vec![parent.as_str()],
+            Value::Array(parents) => parents.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        for parent in extends {
+            if let Some(parent_path) = find_extended_config(root, config_dir, parent) {
+                self.apply_file(root, &parent_path, depth + 1);
+            }
+        }
+
+        let options = &config["compilerOptions"];
+
+        if let Some(base_url) = options["baseUrl"].as_str() {
+            let base_url = fs_utils::normalize_path(&config_dir.join(base_url));
+            self.paths_base = base_url.clone();
+            self.base_url = Some(base_url);
+        }
+
+        if let Some(paths) = options["paths"].as_object() {
+            self.paths = paths
+                .iter()
+                .map(|(pattern, targets)| {
+                    let targets = targets
+                        .as_array()
+                        .map(|t| t.iter().filter_map(Value::as_str).map(str::to_string).collect())
+                        .unwrap_or_default();
+                    (pattern.clone(), targets)
+                })
+                .collect();
+
+            // Without a baseUrl, paths are relative to the file declaring them
+            if self.base_url.is_none() {
+                self.paths_base = config_dir.to_path_buf();
+            }
+        }
+    }
+}
+
+fn find_extended_config(root: &Path, config_dir: &Path, parent: &str) -> Option<PathBuf> {
+    let base = if parent.starts_with('.') || Path::new(parent).is_absolute() {
+        config_dir.join(parent)
+    } else {
+        // A package such as `@tsconfig/strictest`
+        root.join("node_modules").join(parent)
+    };
+
+    let with_json = PathBuf::from(format!("{}.json", base.display()));
+    [base.clone(), with_json, base.join("tsconfig.json")]
+        .into_iter()
+        .find(|candidate| candidate.is_file())
+}
+
+// Matches `@app/*` against `@app/core/auth` and returns what `*` stood for
+fn match_pattern<'a>(pattern: &str, specifier: &'a str) -> Option<&'a str> {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => {
+            let rest = specifier.strip_prefix(prefix)?.strip_suffix(suffix)?;
+            Some(rest)
+        }
+        None if pattern == specifier => Some(""),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, name: &str, content: &str) {
+        let path = root.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn settings_are_inherited_through_extends() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "configs/base.json", r#"{ "compilerOptions": { "baseUrl": "..", "paths": { "@app/*": ["src/app/*"] } } }"#);
+        write(root, "tsconfig.json", r#"{ "extends": "./configs/base" }"#);
+
+        let config = TsConfig::load(root).unwrap();
+        assert_eq!(config.candidates("@app/core"), vec![root.join("src/app/core"), root.join("@app/core")]);
+    }
+
+    #[test]
+    fn a_config_overrides_the_paths_it_extends() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "tsconfig.base.json", r#"{ "compilerOptions": { "paths": { "@app/*": ["src/app/*"] } } }"#);
+        write(root, "tsconfig.json", r#"{ "extends": "./tsconfig.base.json", "compilerOptions": { "paths": { "@lib/*": ["libs/*"] } } }"#);
+
+        let config = TsConfig::load(root).unwrap();
+        assert!(config.candidates("@app/core").is_empty());
+        assert_eq!(config.candidates("@lib/ui"), vec![root.join("libs/ui")]);
+    }
+
+    #[test]
+    fn paths_are_tried_before_base_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "tsconfig.json", r#"{ "compilerOptions": { "baseUrl": "src", "paths": { "shared": ["libs/shared/index.ts"] } } }"#);
+
+        let config = TsConfig::load(root).unwrap();
+        assert_eq!(config.candidates("shared"), vec![root.join("src/libs/shared/index.ts"), root.join("src/shared")]);
+    }
+
+    #[test]
+    fn the_pattern_with_the_longest_prefix_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "tsconfig.json", r#"{
+            "compilerOptions": {
+                "paths": {
+                    "@app/*": ["src/app/*"],
+                    "@app/core/*": ["libs/core/src/*"],
+                    "*": ["types/*"]
+                }
+            }
+        }"#);
+
+        let config = TsConfig::load(root).unwrap();
+        assert_eq!(config.candidates("@app/core/auth"), vec![root.join("libs/core/src/auth")]);
+        assert_eq!(config.candidates("@app/shell"), vec![root.join("src/app/shell")]);
+        assert_eq!(config.candidates("lodash"), vec![root.join("types/lodash")]);
+    }
+
+    #[test]
+    fn paths_without_base_url_are_relative_to_their_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "configs/paths.json", r#"{ "compilerOptions": { "paths": { "@env": ["../env/prod.ts"] } } }"#);
+        write(root, "tsconfig.json", r#"{ "extends": "./configs/paths.json" }"#);
+
+        let config = TsConfig::load(root).unwrap();
+        assert_eq!(config.candidates("@env"), vec![root.join("env/prod.ts")]);
+    }
+
+    #[test]
+    fn extends_cycles_end() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "tsconfig.json", r#"{ "extends": "./other.json", "compilerOptions": { "baseUrl": "." } }"#);
+        write(root, "other.json", r#"{ "extends": "./tsconfig.json" }"#);
+
+        let config = TsConfig::load(root).unwrap();
+        assert_eq!(config.base_url, Some(root.to_path_buf()));
+    }
+
+    #[test]
+    fn comments_and_trailing_commas_are_allowed() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "tsconfig.json", "{\n  // Aliases\n  \"compilerOptions\": {\n    /* root */ \"baseUrl\": \"./src\",\n  },\n}");
+
+        let config = TsConfig::load(root).unwrap();
+        assert_eq!(config.base_url, Some(root.join("src")));
+    }
+}
@@ -0,0 +1,286 @@
+// src/typescript/ast.rs
+
+use serde::{ Deserialize, Serialize };
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SourceFile {
+    pub imports: Vec<ImportDecl>,
+    pub classes: Vec<ClassDecl>,
+    pub functions: Vec<FunctionDecl>,
+    pub variables: Vec<VariableDecl>,
+    pub expressions: Vec<Expr>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportDecl {
+    pub specifier: String,
+    pub kind: ImportKind,
+    pub default: Option<String>,
+    pub namespace: Option<String>,
+    // `export * from 'x'` is a single specifier named `*`
+    pub named: Vec<ImportSpecifier>,
+    pub type_only: bool,
+    // `import('x')`, as opposed to a static import or `require('x')`
+    pub dynamic: bool,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportKind {
+    // `import ... from 'x'`, `import 'x'` and `import('x')`
+    Import,
+    // `export ... from 'x'`
+    ReExport,
+    // `require('x')`
+    Require,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportSpecifier {
+    pub name: String,
+    pub alias: Option<String>,
+    pub type_only: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClassDecl {
+    pub name: String,
+    pub exported: bool,
+    pub decorators: Vec<Decorator>,
+    pub members: Vec<ClassMember>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Decorator {
+    pub name: String,
+    pub args: Vec<Expr>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MemberKind {
+    Property,
+    Method,
+    Getter,
+    Setter,
+    Constructor,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClassMember {
+    pub name: String,
+    pub kind: MemberKind,
+    pub decorators: Vec<Decorator>,
+    pub type_annotation: Option<String>,
+    pub initializer: Option<Expr>,
+    pub params: Vec<Parameter>,
+    pub body: Option<Expr>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Parameter {
+    pub decorators: Vec<Decorator>,
+    pub type_annotation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FunctionDecl {
+    pub name: String,
+    pub body: Expr,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VariableDecl {
+    pub name: String,
+    pub type_annotation: Option<String>,
+    pub initializer: Option<Expr>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Expr {
+    Str(String),
+    Template(String),
+    Number(String),
+    Ident(String),
+    Member {
+        object: Box<Expr>,
+        property: String,
+    },
+    Call {
+        callee: Box<Expr>,
+        type_args: Option<String>,
+        args: Vec<Expr>,
+    },
+    New {
+        callee: Box<Expr>,
+        type_args: Option<String>,
+        args: Vec<Expr>,
+    },
+    Object(ObjectLit),
+    Array(Vec<Expr>),
+    Arrow {
+        params: Vec<String>,
+        body: Box<Expr>,
+    },
+    // Expressions returned or assigned inside a function body
+    Block(Vec<Expr>),
+    Spread(Box<Expr>),
+    // Anything the parser does not model, kept as source text
+    Other(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectLit {
+    pub props: Vec<Property>,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Property {
+    pub key: String,
+    pub value: Expr,
+}
+
+impl SourceFile {
+    pub fn decorated_classes<'a>(&'a self, decorator: &'a str) -> impl Iterator<Item = &'a ClassDecl> {
+        self.classes.iter().filter(move |c| c.decorator(decorator).is_some())
+    }
+
+    pub fn first_exported_class(&self) -> Option<&ClassDecl> {
+        self.classes.iter().find(|c| c.exported)
+    }
+
+    pub fn variable(&self, name: &str) -> Option<&VariableDecl> {
+        self.variables.iter().find(|v| v.name == name)
+    }
+
+    // Follows a bare identifier to the initializer of a top-level variable in
+    // the same file, e.g. `imports: SHARED_IMPORTS`.
+    pub fn resolve_local<'a>(&'a self, expr: &'a Expr) -> &'a Expr {
+        if let Expr::Ident(name) = expr
+            && let Some(init) = self.variable(name).and_then(|v| v.initializer.as_ref())
+        {
+            return init;
+        }
+        expr
+    }
+}
+
+impl ClassDecl {
+    pub fn decorator(&self, name: &str) -> Option<&Decorator> {
+        self.decorators.iter().find(|d| d.name == name)
+    }
+
+    pub fn constructor(&self) -> Option<&ClassMember> {
+        self.members.iter().find(|m| m.kind == MemberKind::Constructor)
+    }
+}
+
+impl Parameter {
+    pub fn decorator(&self, name: &str) -> Option<&Decorator> {
+        self.decorators.iter().find(|d| d.name == name)
+    }
+}
+
+impl Decorator {
+    // The object literal passed to decorators such as `@Component({ ... })`
+    pub fn metadata(&self) -> Option<&ObjectLit> {
+        match self.args.first() {
+            Some(Expr::Object(obj)) => Some(obj),
+            _ => None,
+        }
+    }
+}
+
+impl ObjectLit {
+    pub fn get(&self, key: &str) -> Option<&Expr> {
+        self.props.iter().find(|p| p.key == key).map(|p| &p.value)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(Expr::as_str)
+    }
+}
+
+impl Expr {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Expr::Str(s) | Expr::Template(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    // Dotted name of identifier and member chains, e.g. `RouterModule.forRoot`
+    pub fn path(&self) -> Option<String> {
+        match self {
+            Expr::Ident(name) => Some(name.clone()),
+            Expr::Member { object, property } => object.path().map(|p| format!("{}.{}", p, property)),
+            _ => None,
+        }
+    }
+
+    // The leading identifier that names what an expression refers to:
+    // `RouterModule.forRoot(routes)` and `...SHARED` both name their first symbol.
+    pub fn symbol(&self) -> Option<String> {
+        match self {
+            Expr::Ident(name) => Some(name.clone()),
+            Expr::Member { object, .. } => object.symbol(),
+            Expr::Call { callee, .. } | Expr::New { callee, .. } => callee.symbol(),
+            Expr::Spread(inner) => inner.symbol(),
+            _ => None,
+        }
+    }
+
+    pub fn callee_path(&self) -> Option<String> {
+        match self {
+            Expr::Call { callee, .. } => callee.path(),
+            _ => None,
+        }
+    }
+
+    pub fn call_args(&self) -> &[Expr] {
+        match self {
+            Expr::Call { args, .. } | Expr::New { args, .. } => args,
+            _ => &[],
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Expr]> {
+        match self {
+            Expr::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&ObjectLit> {
+        match self {
+            Expr::Object(obj) => Some(obj),
+            _ => None,
+        }
+    }
+
+    // Visits this expression and every expression nested inside it
+    pub fn walk<'a>(&'a self, visit: &mut dyn FnMut(&'a Expr)) {
+        visit(self);
+        match self {
+            Expr::Member { object, .. } => object.walk(visit),
+            Expr::Call { callee, args, .. } | Expr::New { callee, args, .. } => {
+                callee.walk(visit);
+                for arg in args {
+                    arg.walk(visit);
+                }
+            }
+            Expr::Object(obj) => {
+                for prop in &obj.props {
+                    prop.value.walk(visit);
+                }
+            }
+            Expr::Array(items) | Expr::Block(items) => {
+                for item in items {
+                    item.walk(visit);
+                }
+            }
+            Expr::Arrow { body, .. } | Expr::Spread(body) => body.walk(visit),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,400 @@
+// src/typescript/lexer.rs
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Ident,
+    Str,
+    Template,
+    Number,
+    Regex,
+    Punct,
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    pub line: usize,
+    pub newline_before: bool,
+}
+
+impl Token {
+    pub fn is_punct(&self, p: &str) -> bool {
+        self.kind == TokenKind::Punct && self.text == p
+    }
+
+    pub fn is_ident(&self, name: &str) -> bool {
+        self.kind == TokenKind::Ident && self.text == name
+    }
+}
+
+// Multi-character punctuators the parser cares about. Everything else is
+// emitted one character at a time.
+const PUNCTUATORS: &[&str] = &["...", "=>", "?."];
+
+// Keywords after which a `/` starts a regular expression rather than a division
+const REGEX_PREFIX_KEYWORDS: &[&str] = &[
+    "return", "typeof", "instanceof", "in", "of", "new", "delete", "void", "throw", "case", "do",
+    "else", "yield", "await",
+];
+
+pub fn tokenize(source: &str) -> Vec<Token> {
+    Lexer::new(source).run()
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    newline_before: bool,
+    tokens: Vec<Token>,
+}
+
+impl Lexer {
+    fn new(source: &str) -> Self {
+        Self {
+            chars: source.chars().collect(),
+            pos: 0,
+            line: 1,
+            newline_before: false,
+            tokens: Vec::new(),
+        }
+    }
+
+    fn run(mut self) -> Vec<Token> {
+        while let Some(c) = self.peek(0) {
+            if c == '\n' {
+                self.line += 1;
+                self.newline_before = true;
+                self.pos += 1;
+            } else if c.is_whitespace() {
+                self.pos += 1;
+            } else if c == '/' && self.peek(1) == Some('/') {
+                self.skip_line_comment();
+            } else if c == '/' && self.peek(1) == Some('*') {
+                self.skip_block_comment();
+            } else if c == '\'' || c == '"' {
+                let line = self.line;
+                let value = self.read_string(c);
+                self.push(TokenKind::Str, value, line);
+            } else if c == '`' {
+                let line = self.line;
+                let value = self.read_template();
+                self.push(TokenKind::Template, value, line);
+            } else if c.is_ascii_digit() || (c == '.' && self.peek(1).is_some_and(|n| n.is_ascii_digit())) {
+                let line = self.line;
+                let value = self.read_while(|ch| ch.is_alphanumeric() || ch == '.' || ch == '_');
+                self.push(TokenKind::Number, value, line);
+            } else if is_ident_start(c) {
+                let line = self.line;
+                let value = self.read_while(is_ident_part);
+                self.push(TokenKind::Ident, value, line);
+            } else if c == '/' && self.regex_allowed() {
+                let line = self.line;
+                let value = self.read_regex();
+                self.push(TokenKind::Regex, value, line);
+            } else {
+                let line = self.line;
+                let value = self.read_punct();
+                self.push(TokenKind::Punct, value, line);
+            }
+        }
+
+        self.tokens
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn push(&mut self, kind: TokenKind, text: String, line: usize) {
+        self.tokens.push(Token {
+            kind,
+            text,
+            line,
+            newline_before: self.newline_before,
+        });
+        self.newline_before = false;
+    }
+
+    fn read_while(&mut self, pred: impl Fn(char) -> bool) -> String {
+        let start = self.pos;
+        while self.peek(0).is_some_and(&pred) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn skip_line_comment(&mut self) {
+        while let Some(c) = self.peek(0) {
+            if c == '\n' {
+                break;
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn skip_block_comment(&mut self) {
+        self.pos += 2;
+        while let Some(c) = self.peek(0) {
+            if c == '*' && self.peek(1) == Some('/') {
+                self.pos += 2;
+                return;
+            }
+            if c == '\n' {
+                self.line += 1;
+                self.newline_before = true;
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn read_string(&mut self, quote: char) -> String {
+        let mut value = String::new();
+        self.pos += 1;
+
+        while let Some(c) = self.peek(0) {
+            self.pos += 1;
+            match c {
+                '\\' => {
+                    if let Some(escaped) = self.peek(0) {
+                        self.pos += 1;
+                        match escaped {
+                            'n' => value.push('\n'),
+                            't' => value.push('\t'),
+                            '\n' => self.line += 1,
+                            other => value.push(other),
+                        }
+                    }
+                }
+                // Unterminated string: stop at the end of the line, which is
+                // left for the caller to count
+                '\n' => {
+                    self.pos -= 1;
+                    break;
+                }
+                c if c == quote => break,
+                c => value.push(c),
+            }
+        }
+
+        value
+    }
+
+    // Reads a template literal and returns its raw contents. Substitutions are
+    // kept verbatim, including any nested strings or templates they contain.
+    fn read_template(&mut self) -> String {
+        let start = self.pos + 1;
+        self.pos += 1;
+
+        while let Some(c) = self.peek(0) {
+            match c {
+                '\\' => self.pos += 2,
+                '`' => {
+                    let value = self.chars[start..self.pos].iter().collect();
+                    self.pos += 1;
+                    return value;
+                }
+                '$' if self.peek(1) == Some('{') => {
+                    self.pos += 2;
+                    self.skip_substitution();
+                }
+                '\n' => {
+                    self.line += 1;
+                    self.pos += 1;
+                }
+                _ => self.pos += 1,
+            }
+        }
+
+        self.chars[start.min(self.chars.len())..].iter().collect()
+    }
+
+    fn skip_substitution(&mut self) {
+        let mut depth = 1;
+
+        while let Some(c) = self.peek(0) {
+            match c {
+                '{' => {
+                    depth += 1;
+                    self.pos += 1;
+                }
+                '}' => {
+                    depth -= 1;
+                    self.pos += 1;
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                '\'' | '"' => {
+                    self.read_string(c);
+                }
+                '`' => {
+                    self.read_template();
+                }
+                '\n' => {
+                    self.line += 1;
+                    self.pos += 1;
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn regex_allowed(&self) -> bool {
+        match self.tokens.last() {
+            None => true,
+            Some(prev) => match prev.kind {
+                TokenKind::Punct => !matches!(prev.text.as_str(), ")" | "]" | "}"),
+                TokenKind::Ident => REGEX_PREFIX_KEYWORDS.contains(&prev.text.as_str()),
+                _ => false,
+            },
+        }
+    }
+
+    fn read_regex(&mut self) -> String {
+        let start = self.pos;
+        let mut in_class = false;
+        self.pos += 1;
+
+        while let Some(c) = self.peek(0) {
+            match c {
+                '\\' => self.pos += 2,
+                '[' => {
+                    in_class = true;
+                    self.pos += 1;
+                }
+                ']' => {
+                    in_class = false;
+                    self.pos += 1;
+                }
+                '/' if !in_class => {
+                    self.pos += 1;
+                    break;
+                }
+                '\n' => break,
+                _ => self.pos += 1,
+            }
+        }
+
+        // Flags
+        while self.peek(0).is_some_and(|c| c.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+
+        self.chars[start..self.pos.min(self.chars.len())].iter().collect()
+    }
+
+    fn read_punct(&mut self) -> String {
+        for punct in PUNCTUATORS {
+            let matches = punct
+                .chars()
+                .enumerate()
+                .all(|(i, p)| self.peek(i) == Some(p));
+            // `?.5` is a conditional followed by a number, not optional chaining
+            if matches && !(*punct == "?." && self.peek(2).is_some_and(|c| c.is_ascii_digit())) {
+                self.pos += punct.len();
+                return punct.to_string();
+            }
+        }
+
+        let c = self.chars[self.pos];
+        self.pos += 1;
+        c.to_string()
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$' || c == '#'
+}
+
+fn is_ident_part(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds_and_texts(source: &str) -> Vec<(TokenKind, String)> {
+        tokenize(source).into_iter().map(|t| (t.kind, t.text)).collect()
+    }
+
+    fn puncts(source: &str) -> Vec<String> {
+        tokenize(source).into_iter().filter(|t| t.kind == TokenKind::Punct).map(|t| t.text).collect()
+    }
+
+    #[test]
+    fn comments_are_skipped_and_counted_as_lines() {
+        let tokens = tokenize("// a { comment\nconst /* { block\n } */ a = 1; /** @Component({ */\nb");
+
+        let texts: Vec<&str> = tokens.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, ["const", "a", "=", "1", ";", "b"]);
+        assert_eq!(tokens[0].line, 2);
+        assert_eq!(tokens[1].line, 3);
+        assert!(tokens[1].newline_before);
+        assert_eq!(tokens[5].line, 4);
+    }
+
+    #[test]
+    fn comment_markers_inside_strings_are_text() {
+        assert_eq!(
+            kinds_and_texts("'http://a' \"/* b */\""),
+            [(TokenKind::Str, "http://a".to_string()), (TokenKind::Str, "/* b */".to_string())]
+        );
+    }
+
+    #[test]
+    fn braces_inside_strings_templates_and_regexes_are_not_punctuation() {
+        assert_eq!(puncts("a('{', \"}\", '\\'{')"), ["(", ",", ",", ")"]);
+        assert_eq!(puncts("t = `{ ${ { a: '}' }[`}`] } }`;"), ["=", ";"]);
+        assert_eq!(puncts("r = /[{}]+\\/}/g;"), ["=", ";"]);
+    }
+
+    #[test]
+    fn templates_keep_substitutions_verbatim() {
+        let tokens = tokenize("`<p>${ user.name ?? `n/a` }</p>`");
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].kind, TokenKind::Template);
+        assert_eq!(tokens[0].text, "<p>${ user.name ?? `n/a` }</p>");
+    }
+
+    #[test]
+    fn slashes_are_division_after_values_and_regexes_elsewhere() {
+        let division = kinds_and_texts("a / b / c");
+        assert!(division.iter().all(|(kind, _)| *kind != TokenKind::Regex));
+
+        let regex = kinds_and_texts("return /ab+c/i.test(x)");
+        assert_eq!(regex[1], (TokenKind::Regex, "/ab+c/i".to_string()));
+    }
+
+    #[test]
+    fn string_escapes_and_lines() {
+        let tokens = tokenize("'a\\'b\\nc'\n\"d\"");
+
+        assert_eq!(tokens[0].text, "a'b\nc");
+        assert_eq!(tokens[1].line, 2);
+    }
+
+    #[test]
+    fn truncated_input_does_not_panic() {
+        let sources = [
+            "'unterminated", "\"a\\", "`a ${ b", "`a\\", "/* open", "// open", "/[a-", "/a\\", "a ?", "@",
+            "x = 1.", "`${'", "`${`",
+        ];
+        for source in sources {
+            tokenize(source);
+        }
+    }
+
+    #[test]
+    fn unterminated_strings_stop_at_the_end_of_the_line() {
+        let tokens = tokenize("const a = 'open\nconst b = 1;");
+
+        assert_eq!((tokens[3].kind, tokens[3].text.as_str()), (TokenKind::Str, "open"));
+        assert!(tokens[4].is_ident("const"));
+        assert!(tokens[4].newline_before);
+        assert_eq!(tokens[4].line, 2);
+    }
+}
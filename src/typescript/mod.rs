@@ -0,0 +1,11 @@
+// src/typescript/mod.rs
+pub mod ast;
+pub mod lexer;
+pub mod parser;
+
+pub use ast::*;
+
+pub fn parse(source: &str) -> SourceFile {
+    let tokens = lexer::tokenize(source);
+    parser::Parser::new(&tokens).parse_source_file()
+}
@@ -0,0 +1,1610 @@
+// src/typescript/parser.rs
+//
+// A tolerant recursive-descent parser for the subset of TypeScript the
+// analyzer cares about: imports, decorated classes and their members,
+// top-level variables and the expressions used in Angular metadata.
+// Constructs it does not model are skipped with bracket balancing, so an
+// unusual statement never derails the rest of the file.
+
+use super::ast::*;
+use super::lexer::{Token, TokenKind};
+
+const MODIFIERS: &[&str] = &[
+    "public", "private", "protected", "readonly", "static", "abstract", "override", "declare",
+    "async", "accessor",
+];
+
+const BINARY_OPERATORS: &[&str] = &[
+    "+", "-", "*", "/", "%", "=", "!", "<", ">", "&", "|", "^", "?", ":", "~",
+];
+
+pub struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(tokens: &'a [Token]) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    pub fn parse_source_file(mut self) -> SourceFile {
+        let mut file = SourceFile::default();
+        let mut decorators = Vec::new();
+        let mut exported = false;
+
+        while let Some(token) = self.peek(0) {
+            let line = token.line;
+
+            if token.is_punct("@") {
+                decorators.push(self.parse_decorator());
+                continue;
+            }
+
+            if token.kind != TokenKind::Ident {
+                if matches!(token.text.as_str(), "{" | "(" | "[") {
+                    self.skip_balanced();
+                } else {
+                    self.advance();
+                }
+                exported = false;
+                continue;
+            }
+
+            match token.text.as_str() {
+                "import" if !self.peek_is_punct(1, "(") && !self.peek_is_punct(1, ".") => {
+                    if let Some(import) = self.parse_import(line) {
+                        file.imports.push(import);
+                    }
+                }
+                "export" => {
+                    self.advance();
+                    exported = true;
+                    // `export type { A } from 'x'`
+                    let type_only = self.peek_is_ident(0, "type")
+                        && (self.peek_is_punct(1, "{") || self.peek_is_punct(1, "*"));
+                    if type_only {
+                        self.advance();
+                    }
+                    if self.peek_is_punct(0, "{") || self.peek_is_punct(0, "*") {
+                        if let Some(mut reexport) = self.parse_export_list(line) {
+                            reexport.type_only = type_only;
+                            file.imports.push(reexport);
+                        }
+                        exported = false;
+                        continue;
+                    }
+                    if self.peek_is_ident(0, "default") {
+                        self.advance();
+                        if !self.peek_is_ident(0, "class") && !self.peek_is_ident(0, "abstract") {
+                            let initializer = self.parse_expr();
+                            file.variables.push(VariableDecl {
+                                name: "default".to_string(),
+                                type_annotation: None,
+                                initializer: Some(initializer),
+                            });
+                            exported = false;
+                        }
+                    }
+                    continue;
+                }
+                "abstract" | "declare" => {
+                    self.advance();
+                    continue;
+                }
+                "class" => {
+                    let class = self.parse_class(exported, std::mem::take(&mut decorators));
+                    file.classes.push(class);
+                }
+                "const" | "let" | "var" => {
+                    self.advance();
+                    // `const enum`
+                    if self.peek_is_ident(0, "enum") {
+                        continue;
+                    }
+                    self.parse_variable_declarations(&mut file.variables);
+                }
+                "function" | "async" if self.peek_is_ident(0, "function") || self.peek_is_ident(1, "function") => {
+                    if let Some(function) = self.parse_function() {
+                        file.functions.push(function);
+                    }
+                }
+                "interface" | "enum" | "namespace" | "module" | "type" => {
+                    self.skip_declaration();
+                }
+                _ if is_keyword(&token.text) => {
+                    self.advance();
+                }
+                _ => {
+                    let expr = self.parse_expr();
+                    file.expressions.push(expr);
+                }
+            }
+
+            exported = false;
+        }
+
+        file.imports.extend(self.dynamic_imports());
+        file.imports.sort_by_key(|import| import.line);
+
+        file
+    }
+
+    // ---- token helpers ----
+
+    fn peek(&self, offset: usize) -> Option<&'a Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn peek_is_punct(&self, offset: usize, p: &str) -> bool {
+        self.peek(offset).is_some_and(|t| t.is_punct(p))
+    }
+
+    fn peek_is_ident(&self, offset: usize, name: &str) -> bool {
+        self.peek(offset).is_some_and(|t| t.is_ident(name))
+    }
+
+    // Steps past the current token, but never past the end of the input, so
+    // `pos` stays a valid slice bound in truncated files
+    fn advance(&mut self) {
+        self.pos = (self.pos + 1).min(self.tokens.len());
+    }
+
+    fn eat_punct(&mut self, p: &str) -> bool {
+        if self.peek_is_punct(0, p) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_ident(&mut self, name: &str) -> bool {
+        if self.peek_is_ident(0, name) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn current_line(&self) -> usize {
+        self.peek(0)
+            .or_else(|| self.tokens.last())
+            .map(|t| t.line)
+            .unwrap_or(1)
+    }
+
+    // Skips a bracketed group starting at the current token, or a single token
+    fn skip_balanced(&mut self) {
+        let mut depth = 0usize;
+
+        while let Some(token) = self.peek(0) {
+            self.advance();
+            if token.kind != TokenKind::Punct {
+                if depth == 0 {
+                    return;
+                }
+                continue;
+            }
+            match token.text.as_str() {
+                "{" | "(" | "[" => depth += 1,
+                "}" | ")" | "]" => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                _ if depth == 0 => return,
+                _ => {}
+            }
+        }
+    }
+
+    // Skips `interface`, `enum`, `namespace` and `type` declarations
+    fn skip_declaration(&mut self) {
+        let is_type_alias = self.peek_is_ident(0, "type");
+        self.advance();
+
+        if is_type_alias {
+            // `type` used as an identifier, e.g. `type = 1`
+            if !self.peek(0).is_some_and(|t| t.kind == TokenKind::Ident) {
+                return;
+            }
+            self.advance();
+            if self.peek_is_punct(0, "<") {
+                self.skip_angle_brackets();
+            }
+            if self.eat_punct("=") {
+                self.skip_type();
+            }
+            return;
+        }
+
+        while let Some(token) = self.peek(0) {
+            if token.is_punct("{") {
+                self.skip_balanced();
+                return;
+            }
+            if token.is_punct(";") {
+                self.advance();
+                return;
+            }
+            if token.is_punct("(") || token.is_punct("[") {
+                self.skip_balanced();
+            } else if token.is_punct(":") {
+                // Return type annotation of a function
+                self.advance();
+                self.skip_type();
+            } else {
+                self.advance();
+            }
+        }
+    }
+
+    fn skip_angle_brackets(&mut self) {
+        let mut depth = 0usize;
+        while let Some(token) = self.peek(0) {
+            self.advance();
+            if token.is_punct("<") {
+                depth += 1;
+            } else if token.is_punct(">") {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return;
+                }
+            }
+        }
+    }
+
+    // ---- declarations ----
+
+    fn parse_decorator(&mut self) -> Decorator {
+        self.advance(); // '@'
+
+        match self.parse_postfix_expr() {
+            Expr::Call { callee, args, .. } => Decorator {
+                name: callee.path().unwrap_or_default(),
+                args,
+            },
+            other => Decorator {
+                name: other.path().unwrap_or_default(),
+                args: Vec::new(),
+            },
+        }
+    }
+
+    fn parse_import(&mut self, line: usize) -> Option<ImportDecl> {
+        self.advance(); // 'import'
+
+        let mut import = ImportDecl {
+            specifier: String::new(),
+            kind: ImportKind::Import,
+            default: None,
+            namespace: None,
+            named: Vec::new(),
+            type_only: false,
+            dynamic: false,
+            line,
+        };
+
+        // `import type { A } from 'x'` but not `import type from 'x'`
+        if self.peek_is_ident(0, "type") && !self.peek_is_ident(1, "from") && !self.peek_is_punct(1, ",") {
+            self.advance();
+            import.type_only = true;
+        }
+
+        if let Some(token) = self.peek(0)
+            && token.kind == TokenKind::Str
+        {
+            import.specifier = token.text.clone();
+            self.advance();
+            self.eat_punct(";");
+            return Some(import);
+        }
+
+        loop {
+            let token = self.peek(0)?.clone();
+            if token.is_punct("{") {
+                self.advance();
+                import.named = self.parse_import_specifiers();
+            } else if token.is_punct("*") {
+                self.advance();
+                self.eat_ident("as");
+                import.namespace = self.peek(0).map(|t| t.text.clone());
+                self.advance();
+            } else if token.is_ident("from") {
+                self.advance();
+                break;
+            } else if token.kind == TokenKind::Ident {
+                import.default = Some(token.text.clone());
+                self.advance();
+            } else if token.is_punct(",") {
+                self.advance();
+            } else {
+                return None;
+            }
+        }
+
+        let token = self.peek(0)?;
+        if token.kind != TokenKind::Str {
+            return None;
+        }
+        import.specifier = token.text.clone();
+        self.advance();
+        self.skip_import_attributes();
+        self.eat_punct(";");
+
+        Some(import)
+    }
+
+    // `{ A, B as C, type D }`, with the opening brace already consumed
+    fn parse_import_specifiers(&mut self) -> Vec<ImportSpecifier> {
+        let mut specifiers = Vec::new();
+
+        while let Some(token) = self.peek(0) {
+            if token.is_punct("}") {
+                self.advance();
+                break;
+            }
+            if token.is_punct(",") {
+                self.advance();
+                continue;
+            }
+            if token.kind != TokenKind::Ident && token.kind != TokenKind::Str {
+                self.advance();
+                continue;
+            }
+
+            let mut type_only = false;
+            if token.is_ident("type")
+                && self.peek(1).is_some_and(|t| t.kind == TokenKind::Ident && t.text != "as")
+            {
+                type_only = true;
+                self.advance();
+            }
+
+            let name = self.peek(0).map(|t| t.text.clone()).unwrap_or_default();
+            self.advance();
+
+            let alias = if self.eat_ident("as") {
+                let alias = self.peek(0).map(|t| t.text.clone());
+                self.advance();
+                alias
+            } else {
+                None
+            };
+
+            specifiers.push(ImportSpecifier {
+                name,
+                alias,
+                type_only,
+            });
+        }
+
+        specifiers
+    }
+
+    // `export * from 'x'`, `export * as ns from 'x'` and `export { a } from 'x'`.
+    // A local `export { a, b }` is skipped and yields nothing.
+    fn parse_export_list(&mut self, line: usize) -> Option<ImportDecl> {
+        let named = if self.eat_punct("*") {
+            let alias = if self.eat_ident("as") {
+                let alias = self.peek(0).map(|t| t.text.clone());
+                self.advance();
+                alias
+            } else {
+                None
+            };
+            vec![ImportSpecifier {
+                name: "*".to_string(),
+                alias,
+                type_only: false,
+            }]
+        } else {
+            self.advance(); // '{'
+            self.parse_import_specifiers()
+        };
+
+        if !self.eat_ident("from") {
+            self.eat_punct(";");
+            return None;
+        }
+
+        let specifier = match self.peek(0) {
+            Some(token) if token.kind == TokenKind::Str => token.text.clone(),
+            _ => return None,
+        };
+        self.advance();
+        self.skip_import_attributes();
+        self.eat_punct(";");
+
+        Some(ImportDecl {
+            specifier,
+            kind: ImportKind::ReExport,
+            default: None,
+            namespace: None,
+            named,
+            type_only: false,
+            dynamic: false,
+            line,
+        })
+    }
+
+    // `with { type: 'json' }` / `assert { ... }`
+    fn skip_import_attributes(&mut self) {
+        if (self.peek_is_ident(0, "with") || self.peek_is_ident(0, "assert")) && self.peek_is_punct(1, "{") {
+            self.advance();
+            self.skip_balanced();
+        }
+    }
+
+    // `import('x')` and `require('x')` anywhere in the file, including inside
+    // function bodies the parser otherwise skips. Only literal specifiers
+    // can be resolved, so computed ones are left out.
+    fn dynamic_imports(&self) -> Vec<ImportDecl> {
+        let mut imports = Vec::new();
+
+        for (i, token) in self.tokens.iter().enumerate() {
+            let kind = match token.text.as_str() {
+                "import" => ImportKind::Import,
+                "require" => ImportKind::Require,
+                _ => continue,
+            };
+            if token.kind != TokenKind::Ident || (i > 0 && self.tokens[i - 1].is_punct(".")) {
+                continue;
+            }
+
+            let [open, specifier, close] = match self.tokens.get(i + 1..i + 4) {
+                Some([open, specifier, close]) => [open, specifier, close],
+                _ => continue,
+            };
+            let literal = specifier.kind == TokenKind::Str
+                || (specifier.kind == TokenKind::Template && !specifier.text.contains("${"));
+            if !open.is_punct("(") || !literal || !(close.is_punct(")") || close.is_punct(",")) {
+                continue;
+            }
+
+            imports.push(ImportDecl {
+                specifier: specifier.text.clone(),
+                kind,
+                default: None,
+                namespace: None,
+                named: Vec::new(),
+                // `typeof import('x')` in a type position
+                type_only: i > 0 && self.tokens[i - 1].is_ident("typeof"),
+                dynamic: kind == ImportKind::Import,
+                line: token.line,
+            });
+        }
+
+        imports
+    }
+
+    fn parse_function(&mut self) -> Option<FunctionDecl> {
+        self.eat_ident("async");
+        self.advance(); // 'function'
+        self.eat_punct("*");
+
+        let name = match self.peek(0) {
+            Some(token) if token.kind == TokenKind::Ident => {
+                self.advance();
+                token.text.clone()
+            }
+            _ => "default".to_string(),
+        };
+
+        if self.peek_is_punct(0, "<") {
+            self.skip_angle_brackets();
+        }
+        if self.peek_is_punct(0, "(") {
+            self.skip_balanced();
+        }
+        if self.eat_punct(":") {
+            self.skip_type();
+        }
+
+        // Overload signatures have no body
+        if !self.peek_is_punct(0, "{") {
+            self.eat_punct(";");
+            return None;
+        }
+
+        Some(FunctionDecl {
+            name,
+            body: self.parse_block(),
+        })
+    }
+
+    // Parses a parameter list starting at `(`, including parameter properties
+    // and decorators such as `@Inject(TOKEN) private readonly api: Api`
+    fn parse_params(&mut self) -> Vec<Parameter> {
+        let mut params = Vec::new();
+        let mut decorators = Vec::new();
+        self.advance(); // '('
+
+        while let Some(token) = self.peek(0) {
+            if token.is_punct(")") {
+                self.advance();
+                break;
+            }
+            if token.is_punct(",") {
+                self.advance();
+                continue;
+            }
+            if token.is_punct("@") {
+                decorators.push(self.parse_decorator());
+                continue;
+            }
+            if token.kind == TokenKind::Ident
+                && MODIFIERS.contains(&token.text.as_str())
+                && self.peek(1).is_some_and(|next| next.kind == TokenKind::Ident)
+            {
+                self.advance();
+                continue;
+            }
+
+            // The name or destructuring pattern
+            self.eat_punct("...");
+            match self.peek(0) {
+                Some(t) if t.is_punct("{") || t.is_punct("[") => self.skip_balanced(),
+                Some(t) if t.kind == TokenKind::Ident => self.advance(),
+                _ => {
+                    self.skip_balanced();
+                    continue;
+                }
+            }
+
+            self.eat_punct("?");
+            let type_annotation = if self.eat_punct(":") { self.parse_type() } else { None };
+            if self.eat_punct("=") {
+                self.parse_expr();
+            }
+
+            params.push(Parameter {
+                decorators: std::mem::take(&mut decorators),
+                type_annotation,
+            });
+        }
+
+        params
+    }
+
+    fn parse_class(&mut self, exported: bool, decorators: Vec<Decorator>) -> ClassDecl {
+        self.advance(); // 'class'
+
+        let name = match self.peek(0) {
+            Some(token) if token.kind == TokenKind::Ident && !matches!(token.text.as_str(), "extends" | "implements") => {
+                self.advance();
+                token.text.clone()
+            }
+            _ => "default".to_string(),
+        };
+
+        // Generic parameters, `extends` and `implements` clauses
+        while let Some(token) = self.peek(0) {
+            if token.is_punct("{") {
+                break;
+            }
+            if token.is_punct("<") {
+                self.skip_angle_brackets();
+            } else if token.is_punct("(") || token.is_punct("[") {
+                self.skip_balanced();
+            } else if token.is_punct(";") {
+                break;
+            } else {
+                self.advance();
+            }
+        }
+
+        let members = if self.eat_punct("{") {
+            self.parse_class_members()
+        } else {
+            Vec::new()
+        };
+
+        ClassDecl {
+            name,
+            exported,
+            decorators,
+            members,
+        }
+    }
+
+    fn parse_class_members(&mut self) -> Vec<ClassMember> {
+        let mut members = Vec::new();
+        let mut decorators = Vec::new();
+
+        while let Some(token) = self.peek(0) {
+            if token.is_punct("}") {
+                self.advance();
+                break;
+            }
+            if token.is_punct(";") || token.is_punct(",") {
+                self.advance();
+                continue;
+            }
+            if token.is_punct("@") {
+                decorators.push(self.parse_decorator());
+                continue;
+            }
+
+            // Modifiers, unless they are themselves the member name (`static = 1`)
+            if token.kind == TokenKind::Ident
+                && MODIFIERS.contains(&token.text.as_str())
+                && self.peek(1).is_some_and(|next| !is_member_name_terminator(next))
+            {
+                self.advance();
+                continue;
+            }
+
+            // `static { ... }` initialization blocks
+            if token.is_punct("{") {
+                self.skip_balanced();
+                decorators.clear();
+                continue;
+            }
+
+            let mut kind = MemberKind::Property;
+            if (token.is_ident("get") || token.is_ident("set"))
+                && self.peek(1).is_some_and(|next| !is_member_name_terminator(next))
+            {
+                kind = if token.is_ident("get") { MemberKind::Getter } else { MemberKind::Setter };
+                self.advance();
+            }
+
+            self.eat_punct("*");
+
+            let name = match self.peek(0) {
+                Some(t) if t.is_punct("[") => {
+                    let start = self.pos;
+                    self.skip_balanced();
+                    join_tokens(&self.tokens[start..self.pos])
+                }
+                Some(t) => {
+                    let text = t.text.clone();
+                    self.advance();
+                    text
+                }
+                None => break,
+            };
+
+            if name == "constructor" && self.peek_is_punct(0, "(") {
+                kind = MemberKind::Constructor;
+            }
+
+            self.eat_punct("?");
+            self.eat_punct("!");
+
+            let mut member = ClassMember {
+                name,
+                kind,
+                decorators: std::mem::take(&mut decorators),
+                type_annotation: None,
+                initializer: None,
+                params: Vec::new(),
+                body: None,
+            };
+
+            if self.peek_is_punct(0, "(") || self.peek_is_punct(0, "<") {
+                if member.kind == MemberKind::Property {
+                    member.kind = MemberKind::Method;
+                }
+                if self.peek_is_punct(0, "<") {
+                    self.skip_angle_brackets();
+                }
+                member.params = self.parse_params();
+                if self.eat_punct(":") {
+                    member.type_annotation = self.parse_type();
+                }
+                if self.peek_is_punct(0, "{") {
+                    member.body = Some(self.parse_block());
+                }
+            } else {
+                if self.eat_punct(":") {
+                    member.type_annotation = self.parse_type();
+                }
+                if self.eat_punct("=") {
+                    member.initializer = Some(self.parse_expr());
+                }
+            }
+
+            members.push(member);
+        }
+
+        members
+    }
+
+    fn parse_variable_declarations(&mut self, out: &mut Vec<VariableDecl>) {
+        loop {
+            // Destructuring patterns are not tracked
+            if self.peek_is_punct(0, "{") || self.peek_is_punct(0, "[") {
+                self.skip_balanced();
+                if self.eat_punct(":") {
+                    self.skip_type();
+                }
+                if self.eat_punct("=") {
+                    self.parse_expr();
+                }
+            } else {
+                let Some(token) = self.peek(0) else { return };
+                if token.kind != TokenKind::Ident {
+                    return;
+                }
+                let name = token.text.clone();
+                self.advance();
+                self.eat_punct("!");
+
+                let type_annotation = if self.eat_punct(":") { self.parse_type() } else { None };
+                let initializer = if self.eat_punct("=") { Some(self.parse_expr()) } else { None };
+
+                out.push(VariableDecl {
+                    name,
+                    type_annotation,
+                    initializer,
+                });
+            }
+
+            if !self.eat_punct(",") {
+                self.eat_punct(";");
+                return;
+            }
+        }
+    }
+
+    // ---- types ----
+
+    fn parse_type(&mut self) -> Option<String> {
+        let start = self.pos;
+        self.skip_type();
+        let text = join_tokens(&self.tokens[start..self.pos]);
+        if text.is_empty() { None } else { Some(text) }
+    }
+
+    // Skips a type annotation, stopping at the `=`, `;`, `,` or closing bracket
+    // that ends it. Newlines end the type unless the previous token continues it.
+    fn skip_type(&mut self) {
+        let mut depth = 0usize;
+        let start = self.pos;
+
+        while let Some(token) = self.peek(0) {
+            let at_start = self.pos == start;
+
+            if depth == 0 {
+                if token.kind == TokenKind::Punct {
+                    match token.text.as_str() {
+                        "=" | ";" | "," | ")" | "]" | "}" | ">" => return,
+                        // A brace after a complete type opens a body, not an object type
+                        "{" if !at_start && !self.type_continues() => return,
+                        _ => {}
+                    }
+                }
+                if !at_start && token.newline_before && !self.type_continues() && !is_type_operator(token) {
+                    return;
+                }
+            }
+
+            if token.kind == TokenKind::Punct {
+                match token.text.as_str() {
+                    "<" | "(" | "[" | "{" => depth += 1,
+                    ">" | ")" | "]" | "}" => depth -= 1,
+                    _ => {}
+                }
+            }
+            self.advance();
+        }
+    }
+
+    // Whether the previous token expects the type to continue
+    fn type_continues(&self) -> bool {
+        self.pos
+            .checked_sub(1)
+            .and_then(|i| self.tokens.get(i))
+            .is_some_and(|prev| is_type_operator(prev) || prev.is_ident("keyof") || prev.is_ident("typeof"))
+    }
+
+    // ---- expressions ----
+
+    pub fn parse_expr(&mut self) -> Expr {
+        let start = self.pos;
+
+        if self.eat_punct("...") {
+            return Expr::Spread(Box::new(self.parse_expr()));
+        }
+
+        let expr = self.parse_postfix_expr();
+
+        // `x as T` and `x satisfies T` keep the underlying expression
+        let mut expr = expr;
+        while self.peek_is_ident(0, "as") || self.peek_is_ident(0, "satisfies") {
+            if self.peek(0).is_some_and(|t| t.newline_before) {
+                break;
+            }
+            self.advance();
+            self.skip_type();
+        }
+
+        if self.at_expression_continuation() {
+            self.skip_rest_of_expression();
+            expr = Expr::Other(join_tokens(&self.tokens[start..self.pos]));
+        }
+
+        if self.pos == start {
+            // Never return without consuming input
+            self.advance();
+            return Expr::Other(join_tokens(&self.tokens[start..self.pos]));
+        }
+
+        expr
+    }
+
+    fn at_expression_continuation(&self) -> bool {
+        let Some(token) = self.peek(0) else { return false };
+        match token.kind {
+            TokenKind::Punct => {
+                BINARY_OPERATORS.contains(&token.text.as_str()) && token.text != ":"
+                    || token.text == "?."
+                    || token.text == "=>"
+            }
+            TokenKind::Ident => {
+                !token.newline_before && matches!(token.text.as_str(), "instanceof" | "in")
+            }
+            _ => false,
+        }
+    }
+
+    fn skip_rest_of_expression(&mut self) {
+        let mut depth = 0usize;
+
+        while let Some(token) = self.peek(0) {
+            if token.kind == TokenKind::Punct {
+                match token.text.as_str() {
+                    "(" | "[" | "{" => depth += 1,
+                    ")" | "]" | "}" => {
+                        if depth == 0 {
+                            return;
+                        }
+                        depth -= 1;
+                    }
+                    "," | ";" if depth == 0 => return,
+                    _ => {}
+                }
+            } else if depth == 0 && token.newline_before && !self.previous_is_operator() {
+                return;
+            }
+            self.advance();
+        }
+    }
+
+    fn previous_is_operator(&self) -> bool {
+        self.pos
+            .checked_sub(1)
+            .and_then(|i| self.tokens.get(i))
+            .is_some_and(|prev| prev.kind == TokenKind::Punct && BINARY_OPERATORS.contains(&prev.text.as_str()))
+    }
+
+    fn parse_postfix_expr(&mut self) -> Expr {
+        let mut expr = self.parse_primary_expr();
+
+        while let Some(token) = self.peek(0) {
+            if token.is_punct(".") || token.is_punct("?.") {
+                if self.peek_is_punct(1, "(") || self.peek_is_punct(1, "[") {
+                    // `fn?.()` / `obj?.[key]`
+                    self.advance();
+                    continue;
+                }
+                self.advance();
+                let property = self.peek(0).map(|t| t.text.clone()).unwrap_or_default();
+                self.advance();
+                expr = Expr::Member {
+                    object: Box::new(expr),
+                    property,
+                };
+            } else if token.is_punct("(") && !token.newline_before {
+                self.advance();
+                let args = self.parse_expr_list(")");
+                expr = Expr::Call {
+                    callee: Box::new(expr),
+                    type_args: None,
+                    args,
+                };
+            } else if token.is_punct("<") && self.type_args_precede_call() {
+                let type_args = self.parse_type_args();
+                self.advance(); // '('
+                let args = self.parse_expr_list(")");
+                expr = Expr::Call {
+                    callee: Box::new(expr),
+                    type_args,
+                    args,
+                };
+            } else if token.is_punct("[") && !token.newline_before {
+                let start = self.pos;
+                self.skip_balanced();
+                let index = join_tokens(&self.tokens[start..self.pos]);
+                expr = Expr::Other(format!("{}{}", expr_text(&expr), index));
+            } else if token.is_punct("!") && !self.peek_is_punct(1, "=") {
+                // Non-null assertion
+                self.advance();
+            } else if token.kind == TokenKind::Template && !token.newline_before {
+                // Tagged template
+                self.advance();
+            } else {
+                break;
+            }
+        }
+
+        expr
+    }
+
+    fn parse_primary_expr(&mut self) -> Expr {
+        let Some(token) = self.peek(0).cloned() else {
+            return Expr::Other(String::new());
+        };
+
+        match token.kind {
+            TokenKind::Str => {
+                self.advance();
+                Expr::Str(token.text)
+            }
+            TokenKind::Template => {
+                self.advance();
+                Expr::Template(token.text)
+            }
+            TokenKind::Number => {
+                self.advance();
+                Expr::Number(token.text)
+            }
+            TokenKind::Regex => {
+                self.advance();
+                Expr::Other(token.text)
+            }
+            TokenKind::Ident => self.parse_ident_expr(token),
+            TokenKind::Punct => match token.text.as_str() {
+                "{" => self.parse_object(),
+                "[" => {
+                    self.advance();
+                    Expr::Array(self.parse_expr_list("]"))
+                }
+                "(" => {
+                    if self.arrow_follows_parens() {
+                        self.parse_arrow()
+                    } else {
+                        self.advance();
+                        let inner = self.parse_expr_list(")");
+                        inner.into_iter().next().unwrap_or(Expr::Other(String::new()))
+                    }
+                }
+                "<" if self.arrow_follows_angle_brackets() => {
+                    self.skip_angle_brackets();
+                    self.parse_arrow()
+                }
+                "!" | "-" | "+" | "~" => {
+                    let start = self.pos;
+                    self.advance();
+                    self.parse_postfix_expr();
+                    Expr::Other(join_tokens(&self.tokens[start..self.pos]))
+                }
+                _ => Expr::Other(String::new()),
+            },
+        }
+    }
+
+    fn parse_ident_expr(&mut self, token: Token) -> Expr {
+        match token.text.as_str() {
+            "new" => {
+                self.advance();
+                let mut callee = self.parse_primary_expr();
+                while self.peek_is_punct(0, ".") {
+                    self.advance();
+                    let property = self.peek(0).map(|t| t.text.clone()).unwrap_or_default();
+                    self.advance();
+                    callee = Expr::Member {
+                        object: Box::new(callee),
+                        property,
+                    };
+                }
+                let type_args = if self.peek_is_punct(0, "<") {
+                    self.parse_type_args()
+                } else {
+                    None
+                };
+                let args = if self.eat_punct("(") {
+                    self.parse_expr_list(")")
+                } else {
+                    Vec::new()
+                };
+                Expr::New {
+                    callee: Box::new(callee),
+                    type_args,
+                    args,
+                }
+            }
+            "async"
+                if self.peek(1).is_some_and(|t| t.kind == TokenKind::Ident && !t.newline_before)
+                    || (self.peek_is_punct(1, "(") && self.arrow_follows_parens_at(1)) =>
+            {
+                self.advance();
+                self.parse_primary_expr()
+            }
+            "function" | "class" => {
+                let start = self.pos;
+                self.advance();
+                while let Some(t) = self.peek(0) {
+                    if t.is_punct("{") {
+                        self.skip_balanced();
+                        break;
+                    }
+                    if t.is_punct("(") {
+                        self.skip_balanced();
+                    } else {
+                        self.advance();
+                    }
+                }
+                Expr::Other(join_tokens(&self.tokens[start..self.pos.min(start + 2)]))
+            }
+            "typeof" | "void" | "delete" | "await" => {
+                let start = self.pos;
+                self.advance();
+                let inner = self.parse_postfix_expr();
+                if token.text == "await" {
+                    inner
+                } else {
+                    Expr::Other(join_tokens(&self.tokens[start..self.pos]))
+                }
+            }
+            _ => {
+                self.advance();
+                if self.peek_is_punct(0, "=>") {
+                    self.advance();
+                    let body = self.parse_arrow_body();
+                    return Expr::Arrow {
+                        params: vec![token.text],
+                        body: Box::new(body),
+                    };
+                }
+                Expr::Ident(token.text)
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Expr {
+        let line = self.current_line();
+        self.advance(); // '{'
+        let mut props = Vec::new();
+
+        while let Some(token) = self.peek(0).cloned() {
+            if token.is_punct("}") {
+                self.advance();
+                break;
+            }
+            if token.is_punct(",") {
+                self.advance();
+                continue;
+            }
+
+            if token.is_punct("...") {
+                self.advance();
+                let value = self.parse_expr();
+                props.push(Property {
+                    key: "...".to_string(),
+                    value: Expr::Spread(Box::new(value)),
+                });
+                continue;
+            }
+
+            // Accessors and async/generator methods
+            if (token.is_ident("get") || token.is_ident("set") || token.is_ident("async"))
+                && self.peek(1).is_some_and(|next| next.kind == TokenKind::Ident || next.kind == TokenKind::Str)
+            {
+                self.advance();
+            }
+            self.eat_punct("*");
+
+            let key = match self.peek(0) {
+                Some(t) if t.is_punct("[") => {
+                    let start = self.pos;
+                    self.skip_balanced();
+                    join_tokens(&self.tokens[start..self.pos])
+                }
+                Some(t) if matches!(t.kind, TokenKind::Ident | TokenKind::Str | TokenKind::Number) => {
+                    let text = t.text.clone();
+                    self.advance();
+                    text
+                }
+                Some(_) => {
+                    // Unexpected token inside an object literal
+                    self.skip_balanced();
+                    continue;
+                }
+                None => break,
+            };
+
+            self.eat_punct("?");
+
+            let value = if self.eat_punct(":") {
+                self.parse_expr()
+            } else if self.peek_is_punct(0, "(") || self.peek_is_punct(0, "<") {
+                // Method shorthand
+                let start = self.pos;
+                if self.peek_is_punct(0, "<") {
+                    self.skip_angle_brackets();
+                }
+                self.skip_balanced();
+                if self.eat_punct(":") {
+                    self.skip_type();
+                }
+                let body = if self.peek_is_punct(0, "{") {
+                    self.parse_block()
+                } else {
+                    Expr::Other(join_tokens(&self.tokens[start..self.pos]))
+                };
+                Expr::Arrow {
+                    params: Vec::new(),
+                    body: Box::new(body),
+                }
+            } else {
+                if self.eat_punct("=") {
+                    // Default value in a shorthand pattern
+                    self.parse_expr();
+                }
+                Expr::Ident(key.clone())
+            };
+
+            props.push(Property {
+                key,
+                value,
+            });
+        }
+
+        Expr::Object(ObjectLit { props, line })
+    }
+
+    // Parses comma separated expressions up to and including `close`
+    fn parse_expr_list(&mut self, close: &str) -> Vec<Expr> {
+        let mut items = Vec::new();
+
+        while let Some(token) = self.peek(0) {
+            if token.is_punct(close) {
+                self.advance();
+                break;
+            }
+            if token.is_punct(",") {
+                self.advance();
+                continue;
+            }
+            if token.kind == TokenKind::Punct && matches!(token.text.as_str(), ")" | "]" | "}" | ";") {
+                // Mismatched closing bracket: give up on this list
+                self.advance();
+                break;
+            }
+            items.push(self.parse_expr());
+        }
+
+        items
+    }
+
+    fn arrow_follows_parens(&self) -> bool {
+        self.arrow_follows_parens_at(0)
+    }
+
+    fn arrow_follows_parens_at(&self, offset: usize) -> bool {
+        let mut depth = 0usize;
+        let mut i = self.pos + offset;
+
+        while let Some(token) = self.tokens.get(i) {
+            if token.kind == TokenKind::Punct {
+                match token.text.as_str() {
+                    "(" | "[" | "{" => depth += 1,
+                    ")" | "]" | "}" => {
+                        depth = depth.saturating_sub(1);
+                        if depth == 0 {
+                            let next = self.tokens.get(i + 1);
+                            return next.is_some_and(|t| t.is_punct("=>") || t.is_punct(":"));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            i += 1;
+        }
+
+        false
+    }
+
+    fn arrow_follows_angle_brackets(&self) -> bool {
+        let mut i = self.pos;
+        let mut depth = 0usize;
+        while let Some(token) = self.tokens.get(i) {
+            if token.is_punct("<") {
+                depth += 1;
+            } else if token.is_punct(">") {
+                depth -= 1;
+                if depth == 0 {
+                    return self.tokens.get(i + 1).is_some_and(|t| t.is_punct("("));
+                }
+            } else if token.kind == TokenKind::Punct && matches!(token.text.as_str(), ";" | "{" | "}" | ")") {
+                return false;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    fn parse_arrow(&mut self) -> Expr {
+        let mut params = Vec::new();
+        let mut depth = 0usize;
+        let mut expect_name = true;
+
+        // Collect top-level parameter names
+        while let Some(token) = self.peek(0) {
+            self.advance();
+            if token.kind == TokenKind::Punct {
+                match token.text.as_str() {
+                    "(" | "[" | "{" | "<" => {
+                        depth += 1;
+                        continue;
+                    }
+                    ")" | "]" | "}" | ">" => {
+                        depth = depth.saturating_sub(1);
+                        if depth == 0 {
+                            break;
+                        }
+                        continue;
+                    }
+                    "," if depth == 1 => {
+                        expect_name = true;
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+            if depth == 1 && expect_name && token.kind == TokenKind::Ident {
+                if !MODIFIERS.contains(&token.text.as_str()) {
+                    params.push(token.text.clone());
+                    expect_name = false;
+                }
+            } else if depth == 1 && token.kind == TokenKind::Punct {
+                expect_name = false;
+            }
+        }
+
+        if self.eat_punct(":") {
+            self.skip_type();
+        }
+        self.eat_punct("=>");
+
+        let body = self.parse_arrow_body();
+        Expr::Arrow {
+            params,
+            body: Box::new(body),
+        }
+    }
+
+    fn parse_arrow_body(&mut self) -> Expr {
+        if self.peek_is_punct(0, "{") {
+            self.parse_block()
+        } else {
+            self.parse_expr()
+        }
+    }
+
+    // Parses a statement block, keeping the values of `return` statements and
+    // the right-hand side of declarations and assignments
+    fn parse_block(&mut self) -> Expr {
+        let mut returns = Vec::new();
+        let mut depth = 0usize;
+
+        while let Some(token) = self.peek(0) {
+            if token.is_punct("{") {
+                depth += 1;
+                self.advance();
+            } else if token.is_punct("}") {
+                self.advance();
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    break;
+                }
+            } else if token.is_ident("return") {
+                self.advance();
+                if !self.peek_is_punct(0, ";") && !self.peek_is_punct(0, "}") {
+                    returns.push(self.parse_expr());
+                }
+            } else if token.is_punct("=") && self.is_assignment() {
+                self.advance();
+                returns.push(self.parse_expr());
+            } else {
+                self.advance();
+            }
+        }
+
+        Expr::Block(returns)
+    }
+
+    // A lone `=`, as opposed to part of `==`, `!=`, `<=` or `>=`
+    fn is_assignment(&self) -> bool {
+        let prev = self.pos.checked_sub(1).and_then(|i| self.tokens.get(i));
+        let joined = |t: &Token| t.kind == TokenKind::Punct && matches!(t.text.as_str(), "=" | "!" | "<" | ">");
+        !prev.is_some_and(joined) && !self.peek(1).is_some_and(|t| t.is_punct("="))
+    }
+
+    // `<T>` directly followed by `(`, as in `input<string>()` or `inject<Foo>(TOKEN)`
+    fn type_args_precede_call(&self) -> bool {
+        let mut depth = 0usize;
+        let mut i = self.pos;
+
+        while let Some(token) = self.tokens.get(i) {
+            if token.kind == TokenKind::Punct {
+                match token.text.as_str() {
+                    "<" | "(" | "[" | "{" => depth += 1,
+                    ">" | ")" | "]" | "}" => {
+                        if depth == 0 {
+                            return false;
+                        }
+                        depth -= 1;
+                        if depth == 0 {
+                            return token.text == ">" && self.tokens.get(i + 1).is_some_and(|t| t.is_punct("("));
+                        }
+                    }
+                    ";" => return false,
+                    _ => {}
+                }
+            }
+            i += 1;
+        }
+
+        false
+    }
+
+    fn parse_type_args(&mut self) -> Option<String> {
+        let start = self.pos;
+        self.skip_angle_brackets();
+        let inner = &self.tokens[start + 1..self.pos.saturating_sub(1).max(start + 1)];
+        let text = join_tokens(inner);
+        if text.is_empty() { None } else { Some(text) }
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    matches!(
+        word,
+        "if" | "else" | "for" | "while" | "do" | "switch" | "case" | "default" | "break" | "continue"
+            | "return" | "throw" | "try" | "catch" | "finally" | "with" | "debugger"
+    )
+}
+
+// Tokens that, following a modifier or accessor keyword, show the keyword is
+// itself being used as the member name.
+fn is_member_name_terminator(token: &Token) -> bool {
+    token.kind == TokenKind::Punct && matches!(token.text.as_str(), "(" | ":" | "=" | ";" | "?" | "!" | "<" | "}")
+}
+
+fn is_type_operator(token: &Token) -> bool {
+    token.kind == TokenKind::Punct && matches!(token.text.as_str(), "|" | "&" | "." | "<" | "," | "?" | ":" | "=>")
+}
+
+fn expr_text(expr: &Expr) -> String {
+    match expr {
+        Expr::Ident(name) => name.clone(),
+        Expr::Other(text) => text.clone(),
+        other => other.path().unwrap_or_default(),
+    }
+}
+
+// Rebuilds readable source text from a token slice
+pub fn join_tokens(tokens: &[Token]) -> String {
+    let mut out = String::new();
+    let mut prev: Option<&Token> = None;
+
+    for token in tokens {
+        if let Some(prev) = prev {
+            let word_pair = prev.kind != TokenKind::Punct && token.kind != TokenKind::Punct;
+            let spaced = (token.kind == TokenKind::Punct && matches!(token.text.as_str(), "|" | "&" | "=>" | "="))
+                || (prev.kind == TokenKind::Punct && matches!(prev.text.as_str(), "|" | "&" | "=>" | "=" | "," | ":"));
+            if word_pair || spaced {
+                out.push(' ');
+            }
+        }
+        match token.kind {
+            TokenKind::Str => {
+                out.push('\'');
+                out.push_str(&token.text);
+                out.push('\'');
+            }
+            TokenKind::Template => {
+                out.push('`');
+                out.push_str(&token.text);
+                out.push('`');
+            }
+            _ => out.push_str(&token.text),
+        }
+        prev = Some(token);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::typescript::{ parse, Expr, ImportKind, MemberKind };
+
+    // Exercises most of the parser: decorators, members, signals, routes,
+    // arrows, generics, templates and regexes
+    const COMPONENT: &str = r#"import { Component, Input, inject } from '@angular/core';
+import type { Routes } from '@angular/router';
+export * from './models';
+
+@Component({
+  selector: 'app-user',
+  standalone: true,
+  imports: [CommonModule, UserCardComponent],
+  template: `<div class="a">{{ name() }}</div>`,
+  styles: [':host { display: block; }'],
+})
+export class UserComponent<T extends object> implements OnInit {
+  @Input({ required: true }) name!: string;
+  readonly count = signal<number>(0);
+  private readonly http = inject(HttpClient, { optional: true });
+  static pattern = /[a-z]+\/\d{2}/g;
+
+  constructor(@Inject(TOKEN) private token: string, public store?: Store<T>) {}
+
+  get label(): string {
+    return typeof this.name === 'string' ? this.name.trim() : `${this.count()}`;
+  }
+
+  ngOnInit(): void {
+    const x = !this.store?.select((s) => s.user)[0];
+    this.items = this.items.filter(item => item.id !== -1).map(async (i) => await load<T>(i));
+  }
+}
+
+export const routes: Routes = [
+  { path: '', component: HomeComponent, canActivate: [() => inject(AuthGuard).canActivate()] },
+  { path: 'lazy', loadChildren: () => import('./lazy/lazy.routes').then(m => m.routes) },
+  { path: '**', redirectTo: '' },
+];
+
+export default function provide(): void {
+  void new Map<string, number>().set('a', 1);
+}
+"#;
+
+    #[test]
+    fn truncated_unary_expressions_do_not_panic() {
+        let sources = [
+            "const x = !a.",
+            "const x = typeof a.",
+            "export class A { b = -c.",
+            "const x = void a?.",
+            "const x = new A.",
+            "const x = await a.b.",
+            "const x = -",
+            "@Component({ selector: !a.",
+        ];
+        for source in sources {
+            parse(source);
+        }
+    }
+
+    #[test]
+    fn every_prefix_of_a_file_parses() {
+        for (end, _) in COMPONENT.char_indices() {
+            parse(&COMPONENT[..end]);
+        }
+    }
+
+    #[test]
+    fn multi_line_decorators_keep_their_metadata() {
+        let source = parse(
+            "@Component({\n  selector:\n    'app-a',\n  imports: [\n    A,\n    B, // trailing\n  ],\n})\nexport class AComponent {}",
+        );
+
+        let class = &source.classes[0];
+        let metadata = class.decorator("Component").and_then(|d| d.metadata()).unwrap();
+        assert_eq!(class.name, "AComponent");
+        assert!(class.exported);
+        assert_eq!(metadata.get_str("selector"), Some("app-a"));
+        assert_eq!(metadata.get("imports").and_then(Expr::as_array).map(<[Expr]>::len), Some(2));
+    }
+
+    #[test]
+    fn comments_between_decorators_and_classes_are_ignored() {
+        let source = parse("/* header */\n@Injectable({ providedIn: 'root' }) // note\n/** doc { */\nexport class Api {}");
+
+        let class = &source.classes[0];
+        assert_eq!(class.name, "Api");
+        assert!(class.decorator("Injectable").is_some());
+    }
+
+    #[test]
+    fn braces_inside_strings_templates_and_regexes_do_not_end_the_class() {
+        let source = parse(
+            "@Component({ selector: 'a', template: `<p>{{ a }}</p>${'}'}`, styles: ['p { color: red }'] })\n\
+             export class A {\n  pattern = /[{}]/;\n  label = '}';\n  run() { return `}`; }\n}\n\
+             export class B {}",
+        );
+
+        let names: Vec<&str> = source.classes.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["A", "B"]);
+        let members: Vec<&str> = source.classes[0].members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(members, ["pattern", "label", "run"]);
+        let metadata = source.classes[0].decorator("Component").and_then(|d| d.metadata()).unwrap();
+        assert_eq!(metadata.get_str("template"), Some("<p>{{ a }}</p>${'}'}"));
+    }
+
+    #[test]
+    fn every_class_of_a_file_gets_its_own_decorators() {
+        let source = parse(
+            "@Pipe({ name: 'a' }) export class APipe {}\n\
+             class Helper {}\n\
+             @Directive({ selector: '[b]' })\n@Other() export class BDirective { @Input() b = ''; }",
+        );
+
+        assert_eq!(source.classes.len(), 3);
+        assert!(source.classes[0].decorator("Pipe").is_some());
+        assert!(source.classes[1].decorators.is_empty());
+        assert!(!source.classes[1].exported);
+        let names: Vec<&str> = source.classes[2].decorators.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["Directive", "Other"]);
+        assert_eq!(source.classes[2].members[0].decorators[0].name, "Input");
+    }
+
+    #[test]
+    fn members_and_constructor_parameters() {
+        let source = parse(&COMPONENT[COMPONENT.find("@Component").unwrap()..]);
+
+        let class = &source.classes[0];
+        let kinds: Vec<(&str, MemberKind)> = class.members.iter().map(|m| (m.name.as_str(), m.kind)).collect();
+        assert_eq!(
+            kinds,
+            [
+                ("name", MemberKind::Property),
+                ("count", MemberKind::Property),
+                ("http", MemberKind::Property),
+                ("pattern", MemberKind::Property),
+                ("constructor", MemberKind::Constructor),
+                ("label", MemberKind::Getter),
+                ("ngOnInit", MemberKind::Method),
+            ]
+        );
+
+        let params = &class.constructor().unwrap().params;
+        assert_eq!(params.len(), 2);
+        assert!(params[0].decorator("Inject").is_some());
+        assert_eq!(params[1].type_annotation.as_deref(), Some("Store<T>"));
+    }
+
+    #[test]
+    fn imports_exports_and_dynamic_imports() {
+        let source = parse(COMPONENT);
+
+        let imports: Vec<(&str, ImportKind, bool)> =
+            source.imports.iter().map(|i| (i.specifier.as_str(), i.kind, i.dynamic)).collect();
+        assert_eq!(
+            imports,
+            [
+                ("@angular/core", ImportKind::Import, false),
+                ("@angular/router", ImportKind::Import, false),
+                ("./models", ImportKind::ReExport, false),
+                ("./lazy/lazy.routes", ImportKind::Import, true),
+            ]
+        );
+        assert!(source.imports[1].type_only);
+        assert!(source.variable("routes").is_some_and(|v| v.initializer.as_ref().and_then(Expr::as_array).is_some()));
+        // `export default function` is kept as the `default` export
+        assert!(source.variable("default").is_some());
+    }
+
+    #[test]
+    fn truncated_declarations_keep_what_was_parsed() {
+        let source = parse("@Component({ selector: 'app-a' })\nexport class A {\n  b = inject(");
+
+        assert_eq!(source.classes.len(), 1);
+        assert_eq!(source.classes[0].name, "A");
+        assert_eq!(source.classes[0].members[0].name, "b");
+
+        let source = parse("import { A } from './a';\nexport const routes: Routes = [{ path: 'x', component:");
+        assert_eq!(source.imports.len(), 1);
+        assert_eq!(source.variables[0].name, "routes");
+    }
+}
@@ -58,7 +58,7 @@ pub mod string_utils {
 pub mod fs_utils {
     use anyhow::{Context, Result};
     use std::fs;
-    use std::path::Path;
+    use std::path::{Component, Path, PathBuf};
     
     #[allow(dead_code)]
     pub fn read_file(path: impl AsRef<Path>) -> Result<String> {
@@ -91,4 +91,124 @@ pub mod fs_utils {
             Err(_) => path.display().to_string(),
         }
     }
+
+    // Resolves `.` and `..` components without touching the file system
+    pub fn normalize_path(path: &Path) -> PathBuf {
+        let mut result = PathBuf::new();
+
+        for component in path.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !result.pop() {
+                        result.push("..");
+                    }
+                }
+                other => result.push(other.as_os_str()),
+            }
+        }
+
+        result
+    }
+}
+
+pub mod json_utils {
+    use anyhow::{Context, Result};
+
+    // Parses JSON with comments and trailing commas, as used by tsconfig.json
+    // and angular.json
+    pub fn parse_jsonc(content: &str) -> Result<serde_json::Value> {
+        serde_json::from_str(&strip_jsonc(content)).context("Invalid JSON")
+    }
+
+    fn strip_jsonc(content: &str) -> String {
+        remove_trailing_commas(&remove_comments(content))
+    }
+
+    fn remove_comments(content: &str) -> String {
+        let chars: Vec<char> = content.chars().collect();
+        let mut out = String::with_capacity(content.len());
+        let mut in_string = false;
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+
+            if in_string {
+                out.push(c);
+                if c == '\\' && i + 1 < chars.len() {
+                    out.push(chars[i + 1]);
+                    i += 1;
+                } else if c == '"' {
+                    in_string = false;
+                }
+                i += 1;
+            } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            } else {
+                in_string = c == '"';
+                out.push(c);
+                i += 1;
+            }
+        }
+
+        out
+    }
+
+    fn remove_trailing_commas(content: &str) -> String {
+        let chars: Vec<char> = content.chars().collect();
+        let mut out = String::with_capacity(content.len());
+        let mut in_string = false;
+        let mut escaped = false;
+
+        for (i, &c) in chars.iter().enumerate() {
+            if in_string {
+                in_string = escaped || c != '"';
+                escaped = !escaped && c == '\\';
+            } else if c == '"' {
+                in_string = true;
+            } else if c == ',' {
+                let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+                if matches!(next, Some('}') | Some(']')) {
+                    continue;
+                }
+            }
+            out.push(c);
+        }
+
+        out
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn comments_and_trailing_commas_are_removed() {
+            let value = parse_jsonc("{\n  // line\n  \"a\": [1, 2,], /* block */\n  \"b\": { \"c\": true, },\n}").unwrap();
+            assert_eq!(value, serde_json::json!({ "a": [1, 2], "b": { "c": true } }));
+        }
+
+        #[test]
+        fn strings_are_left_alone() {
+            let value = parse_jsonc(r#"{ "url": "http://x/*y*/", "quote": "a \"//\" b", "comma": ",]" }"#).unwrap();
+            assert_eq!(value["url"], "http://x/*y*/");
+            assert_eq!(value["quote"], "a \"//\" b");
+            assert_eq!(value["comma"], ",]");
+        }
+
+        #[test]
+        fn invalid_json_is_an_error() {
+            assert!(parse_jsonc("{ \"a\": }").is_err());
+            assert!(parse_jsonc("{ /* unterminated").is_err());
+        }
+    }
 }
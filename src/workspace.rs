@@ -0,0 +1,184 @@
+// src/workspace.rs
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use crate::utils::json_utils;
+
+// The projects of a workspace, as declared in `angular.json` and in Nx
+// `project.json` files
+#[derive(Debug, Default)]
+pub struct Workspace {
+    pub projects: Vec<WorkspaceProject>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WorkspaceProject {
+    pub name: String,
+    // Relative to the workspace root; empty for an app at the root
+    pub root: PathBuf,
+    pub source_root: Option<PathBuf>,
+    pub prefix: Option<String>,
+    pub project_type: ProjectType,
+    // Nx tags such as `scope:shared` or `type:ui`
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectType {
+    Application,
+    Library,
+}
+
+impl Workspace {
+    // Projects from `angular.json` and the given Nx `project.json` files, as
+    // found by the scanner, or None when the root has neither
+    pub fn load(root: &Path, project_json_files: &[PathBuf]) -> Option<Workspace> {
+        let mut workspace = Workspace::default();
+
+        if let Ok(content) = fs::read_to_string(root.join("angular.json"))
+            && let Ok(config) = json_utils::parse_jsonc(&content)
+            && let Some(projects) = config["projects"].as_object()
+        {
+            for (name, project) in projects {
+                // Nx's version 2 angular.json maps names to directories with
+                // a project.json, which is picked up below
+                if project.is_string() {
+                    continue;
+                }
+                workspace.add(project_from_json(name, project, None));
+            }
+        }
+
+        for project_json in project_json_files {
+            let Ok(content) = fs::read_to_string(project_json) else { continue };
+            let Ok(config) = json_utils::parse_jsonc(&content) else { continue };
+
+            let dir = project_json.parent().unwrap_or(root);
+            let relative_dir = dir.strip_prefix(root).unwrap_or(dir).to_path_buf();
+            let name = config["name"]
+                .as_str()
+                .map(str::to_string)
+                .or_else(|| dir.file_name().and_then(|n| n.to_str()).map(str::to_string))
+                .unwrap_or_default();
+
+            workspace.add(project_from_json(&name, &config, Some(relative_dir)));
+        }
+
+        if workspace.projects.is_empty() {
+            None
+        } else {
+            Some(workspace)
+        }
+    }
+
+    // Adds a project, merging tags into one of the same name already known
+    fn add(&mut self, project: WorkspaceProject) {
+        match self.projects.iter_mut().find(|p| p.name == project.name) {
+            Some(existing) => {
+                for tag in project.tags {
+                    if !existing.tags.contains(&tag) {
+                        existing.tags.push(tag);
+                    }
+                }
+            }
+            None => self.projects.push(project),
+        }
+    }
+
+    pub fn project(&self, name: &str) -> Option<&WorkspaceProject> {
+        self.projects.iter().find(|p| p.name == name)
+    }
+
+    // The project a project-relative path belongs to. Nested roots win, so
+    // libraries under `projects/` are not claimed by an app at the root.
+    pub fn project_for(&self, relative_path: &Path) -> Option<&WorkspaceProject> {
+        self.projects
+            .iter()
+            .filter(|p| relative_path.starts_with(&p.root))
+            .max_by_key(|p| p.root.components().count())
+    }
+
+    pub fn project_names(&self) -> Vec<&str> {
+        self.projects.iter().map(|p| p.name.as_str()).collect()
+    }
+}
+
+// A project entry from `angular.json` or a `project.json`, whose own
+// directory is the default root
+fn project_from_json(name: &str, project: &Value, default_root: Option<PathBuf>) -> WorkspaceProject {
+    let path = |key: &str| project[key].as_str().map(|p| PathBuf::from(p.trim_end_matches('/')));
+
+    WorkspaceProject {
+        name: name.to_string(),
+        root: path("root").or(default_root).unwrap_or_default(),
+        source_root: path("sourceRoot"),
+        prefix: project["prefix"].as_str().map(str::to_string),
+        project_type: match project["projectType"] {
+            Value::String(ref t) if t == "library" => ProjectType::Library,
+            _ => ProjectType::Application,
+        },
+        tags: project["tags"]
+            .as_array()
+            .map(|tags| tags.iter().filter_map(Value::as_str).map(str::to_string).collect())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn projects_come_from_angular_json_and_project_json_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("angular.json"), r#"{
+            "projects": {
+                "shop": { "root": "", "sourceRoot": "src", "prefix": "app", "projectType": "application" },
+                "ui": "libs/ui"
+            }
+        }"#).unwrap();
+        fs::create_dir_all(root.join("libs/ui")).unwrap();
+        fs::write(root.join("libs/ui/project.json"), r#"{ "name": "ui", "projectType": "library", "tags": ["type:ui"] }"#).unwrap();
+
+        let workspace = Workspace::load(root, &[root.join("libs/ui/project.json")]).unwrap();
+        assert_eq!(workspace.project_names(), vec!["shop", "ui"]);
+
+        let ui = workspace.project("ui").unwrap();
+        assert_eq!(ui.root, PathBuf::from("libs/ui"));
+        assert_eq!(ui.project_type, ProjectType::Library);
+        assert_eq!(ui.tags, vec!["type:ui"]);
+    }
+
+    #[test]
+    fn a_root_without_projects_is_no_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("angular.json"), r#"{ "projects": {} }"#).unwrap();
+
+        assert!(Workspace::load(dir.path(), &[]).is_none());
+    }
+
+    #[test]
+    fn the_most_nested_project_root_wins() {
+        let project = |name: &str, root: &str| WorkspaceProject {
+            name: name.to_string(),
+            root: PathBuf::from(root),
+            source_root: None,
+            prefix: None,
+            project_type: ProjectType::Application,
+            tags: Vec::new(),
+        };
+        let workspace = Workspace {
+            projects: vec![project("app", ""), project("lib", "projects/lib"), project("nested", "projects/lib/nested")],
+        };
+        let name = |path: &str| workspace.project_for(Path::new(path)).map(|p| p.name.as_str());
+
+        assert_eq!(name("src/main.ts"), Some("app"));
+        assert_eq!(name("projects/lib/src/index.ts"), Some("lib"));
+        assert_eq!(name("projects/lib/nested/a.ts"), Some("nested"));
+        // Whole path components only
+        assert_eq!(name("projects/library/a.ts"), Some("app"));
+    }
+}